@@ -0,0 +1,220 @@
+use std::{collections::{HashMap, HashSet}, fs::File, io::{BufRead, BufReader, BufWriter, Result}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{analyze::load_network, common::process_dump, network_generator::{canonicalize_link, wiki_text::{interlanguage_link, linked_articles, parse_text}, wiki_xml_dump::{Namespaces, WikiPage}}};
+
+/// Where the mapping between the articles of the two editions comes from.
+pub enum LanguageLinkSource {
+    /// The wikitext of the first edition, using its `[[xx:Title]]` links.
+    Dump { xml_dump_path: PathBuf, xml_dump_index_path: PathBuf },
+    /// A table with one `title;language;target title` entry per line.
+    Table(PathBuf)
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct AlignmentStatistics {
+    language: String,
+    number_of_language_links: usize,
+    number_of_aligned_articles: usize,
+    number_of_aligned_links: usize,
+    number_of_links_in_both: usize,
+    missing_in_first: Vec<MissingLink>,
+    missing_in_second: Vec<MissingLink>
+}
+
+/// A link between two aligned articles which exists only in one edition. Titles are given in
+/// both editions so the suggestion can be checked in the edition the link exists in.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct MissingLink {
+    source: String,
+    target: String,
+    source_in_other_edition: String,
+    target_in_other_edition: String
+}
+
+pub fn align(first_network_file_path: PathBuf, second_network_file_path: PathBuf, output_file_path: PathBuf, language: String, language_link_source: LanguageLinkSource) -> Result<()> {
+    let language_links = match language_link_source {
+        LanguageLinkSource::Dump { xml_dump_path, xml_dump_index_path } => read_language_links_from_dump(&xml_dump_path, &xml_dump_index_path, &language)?,
+        LanguageLinkSource::Table(table_path) => read_language_link_table(table_path, &language)?
+    };
+    println!("found {} language links", language_links.len());
+
+    let first_network = load_network(first_network_file_path)?;
+    let second_network = load_network(second_network_file_path)?;
+
+    let alignment = align_articles(&first_network, &second_network, &language_links);
+    println!("aligned {} articles", alignment.len());
+
+    let mut statistics = AlignmentStatistics {
+        language,
+        number_of_language_links: language_links.len(),
+        number_of_aligned_articles: alignment.len(),
+        number_of_aligned_links: 0,
+        number_of_links_in_both: 0,
+        missing_in_first: Vec::new(),
+        missing_in_second: Vec::new()
+    };
+
+    let second_alignment = alignment.iter()
+        .map(|(first, second)| (*second, *first))
+        .collect::<HashMap<&String, &String>>();
+
+    compare_links(&first_network, &second_network, &alignment, &mut statistics.missing_in_second, Some(&mut statistics.number_of_links_in_both));
+    compare_links(&second_network, &first_network, &second_alignment, &mut statistics.missing_in_first, None);
+
+    statistics.number_of_aligned_links = statistics.number_of_links_in_both
+        + statistics.missing_in_first.len()
+        + statistics.missing_in_second.len();
+
+    let output_writer = BufWriter::new(File::create(output_file_path)?);
+    serde_json::to_writer(output_writer, &statistics)?;
+
+    Ok(())
+}
+
+/// Keeps the language links between articles which exist in both networks. Articles which are the
+/// target of more than one language link are ambiguous and left out.
+fn align_articles<'a>(first_network: &'a HashMap<String, Vec<String>>, second_network: &'a HashMap<String, Vec<String>>, language_links: &HashMap<String, String>) -> HashMap<&'a String, &'a String> {
+    let mut targets = HashMap::new();
+
+    for (source, target) in language_links {
+        if let (Some((source, _)), Some((target, _))) = (first_network.get_key_value(source), second_network.get_key_value(target)) {
+            targets.entry(target).or_insert_with(Vec::new).push(source);
+        }
+    }
+
+    targets.into_iter()
+        .filter(|(_, sources)| sources.len() == 1)
+        .map(|(target, sources)| (sources[0], target))
+        .collect()
+}
+
+/// Collects the links between aligned articles of `network` which are missing between their
+/// counterparts in `other_network`.
+fn compare_links(network: &HashMap<String, Vec<String>>, other_network: &HashMap<String, Vec<String>>, alignment: &HashMap<&String, &String>, missing_links: &mut Vec<MissingLink>, mut links_in_both: Option<&mut usize>) {
+    let mut sources = alignment.keys().collect::<Vec<_>>();
+    sources.sort();
+
+    for source in sources {
+        let other_source = alignment[*source];
+        let other_links = other_network[other_source].iter().collect::<HashSet<&String>>();
+        let links = network[*source].iter().collect::<HashSet<&String>>();
+
+        for target in links {
+            if let Some(other_target) = alignment.get(target) {
+                if other_links.contains(other_target) {
+                    if let Some(links_in_both) = links_in_both.as_deref_mut() {
+                        *links_in_both += 1;
+                    }
+                } else {
+                    missing_links.push(MissingLink {
+                        source: other_source.to_string(),
+                        target: other_target.to_string(),
+                        source_in_other_edition: source.to_string(),
+                        target_in_other_edition: target.to_string()
+                    });
+                }
+            }
+        }
+    }
+
+    missing_links.sort_by(|a, b| (&a.source, &a.target).cmp(&(&b.source, &b.target)));
+}
+
+fn read_language_links_from_dump(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, language: &str) -> Result<HashMap<String, String>> {
+    let number_of_threads = 4;
+
+    let process = |page: WikiPage| {
+        let target = page_language_link(&page, language)?;
+        Some((page.title, target))
+    };
+
+    process_dump(xml_dump_path, xml_dump_index_path, number_of_threads, process, |results| {
+        Ok(results.into_iter().collect())
+    })
+}
+
+/// The title of the article in the other edition, taken from the first interlanguage link to
+/// `language` of an article.
+fn page_language_link(page: &WikiPage, language: &str) -> Option<String> {
+    if page.namespace_id != Namespaces::MAIN {
+        return None;
+    }
+
+    let links = parse_text(page)?;
+
+    linked_articles(&links).into_iter()
+        .filter_map(|link| interlanguage_link(link, language))
+        .filter_map(|title| canonicalize_link(&title.to_owned()))
+        .next()
+}
+
+fn read_language_link_table(table_path: PathBuf, language: &str) -> Result<HashMap<String, String>> {
+    let reader = BufReader::new(File::open(table_path)?);
+    let mut language_links = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut parts = line.splitn(3, ';');
+
+        if let (Some(source), Some(link_language), Some(target)) = (parts.next(), parts.next(), parts.next()) {
+            if link_language == language {
+                if let Some(target) = canonicalize_link(&target.to_owned()) {
+                    language_links.insert(source.to_owned(), target);
+                }
+            }
+        }
+    }
+
+    Ok(language_links)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::network_generator::wiki_xml_dump::{Namespaces, WikiPage};
+
+    use super::{align_articles, page_language_link};
+
+    #[test]
+    fn test_page_language_link() {
+        let page = |text: &str| WikiPage { title: "Cologne".to_string(), text: Some(text.to_string()), namespace_id: Namespaces::MAIN };
+
+        assert_eq!(page_language_link(&page("[[Germany]] [[fr:Cologne]] [[de:köln]] [[de:Koeln]]"), "de"), Some("Köln".to_string()));
+        assert_eq!(page_language_link(&page("[[:de:Köln]]"), "de"), None);
+        assert_eq!(page_language_link(&page("[[fr:Cologne]]"), "de"), None);
+    }
+
+    #[test]
+    fn test_align_articles() {
+        let first_network = HashMap::from([
+            ("Cologne".to_string(), vec![]),
+            ("Koeln".to_string(), vec![]),
+            ("Munich".to_string(), vec![]),
+            ("Berlin".to_string(), vec![])
+        ]);
+        let second_network = HashMap::from([
+            ("Köln".to_string(), vec![]),
+            ("München".to_string(), vec![]),
+            ("Berlin".to_string(), vec![])
+        ]);
+        let language_links = HashMap::from([
+            ("Cologne".to_string(), "Köln".to_string()),
+            ("Koeln".to_string(), "Köln".to_string()),
+            ("Munich".to_string(), "München".to_string()),
+            ("Hamburg".to_string(), "Hamburg".to_string())
+        ]);
+
+        let alignment = align_articles(&first_network, &second_network, &language_links);
+        let mut alignment = alignment.into_iter()
+            .map(|(first, second)| (first.as_str(), second.as_str()))
+            .collect::<Vec<(&str, &str)>>();
+        alignment.sort();
+
+        assert_eq!(alignment, vec![("Munich", "München")]);
+    }
+}
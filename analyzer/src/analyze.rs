@@ -1,10 +1,24 @@
-use std::{collections::HashMap, fs::File, io::{BufRead, BufReader, BufWriter, Result}, path::PathBuf};
+use std::{collections::HashMap, fs::File, io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Result}, path::PathBuf};
 
-use crate::statistics::gather_statistics;
+use crate::{node_attributes::load_node_attributes, temporal::load_temporal_network, statistics::{gather_statistics, registry, StatisticsOptions}};
 
-pub fn analyze(network_file_path: PathBuf, statistics_file_path: PathBuf) -> Result<()> {
-    let network = load_network(network_file_path)?;
-    let statistics = gather_statistics(&network);
+pub fn analyze(network_file_path: PathBuf, statistics_file_path: PathBuf, node_file_path: Option<PathBuf>, metric_names: Vec<String>, options: StatisticsOptions, as_of: Option<String>) -> Result<()> {
+    let metrics = registry(&options).into_iter()
+        .filter(|metric| metric_names.iter().any(|name| name == metric.name()))
+        .collect::<Vec<_>>();
+
+    if node_file_path.is_none() {
+        if let Some(metric) = metrics.iter().find(|metric| metric.needs_node_attributes()) {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("the metric {} needs a node attributes file", metric.name())));
+        }
+    }
+
+    let network = match as_of {
+        Some(as_of) => load_temporal_network(network_file_path, &as_of)?,
+        None => load_network(network_file_path)?
+    };
+    let nodes = node_file_path.map(load_node_attributes).transpose()?;
+    let statistics = gather_statistics(&network, nodes.as_ref(), &metrics);
     let statistics_file = File::create(statistics_file_path)?;
     let statistics_writer = BufWriter::new(statistics_file);
 
@@ -12,7 +26,7 @@ pub fn analyze(network_file_path: PathBuf, statistics_file_path: PathBuf) -> Res
     Ok(())
 }
 
-fn load_network(network_file_path: PathBuf) -> Result<HashMap<String, Vec<String>>> {
+pub fn load_network(network_file_path: PathBuf) -> Result<HashMap<String, Vec<String>>> {
     let network_file = File::open(network_file_path)?;
     let network_file_reader = BufReader::new(network_file);
 
@@ -23,7 +37,7 @@ fn load_network(network_file_path: PathBuf) -> Result<HashMap<String, Vec<String
 
         let mut entries = line.split(";");
         if let Some(node) = entries.next() {
-            network.insert(node.to_owned(), Vec::from_iter(entries.map(|s| s.to_owned())));
+            network.insert(node.to_owned(), Vec::from_iter(entries.map(|s| s.trim_start().to_owned())));
         }
         
     }
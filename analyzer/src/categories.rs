@@ -0,0 +1,87 @@
+use std::{collections::{BTreeSet, HashMap, HashSet, VecDeque}, fs::File, io::{BufWriter, Result, Write}, path::PathBuf};
+
+use crate::{analyze::load_network, network_generator::wiki_text::{canonical_category_title, CATEGORY_PREFIX}};
+
+/// The title of a category as in the category network, given with the English or the local name
+/// of the category namespace or just by its name.
+pub fn category_title(category: &str, category_namespace: &str) -> String {
+    canonical_category_title(category, category_namespace).unwrap_or_else(|| format!("{CATEGORY_PREFIX}{}", category.trim()))
+}
+
+/// Articles in the category or in one of its subcategories at most `depth` levels below it, all
+/// levels if no depth is given. Every category is only visited once, so cycles in the category
+/// graph end the search instead of looping forever.
+pub fn articles_below(category_graph: &HashMap<String, Vec<String>>, category: &str, category_namespace: &str, depth: Option<usize>) -> BTreeSet<String> {
+    let mut members: HashMap<&String, Vec<&String>> = HashMap::new();
+
+    for (page, categories) in category_graph {
+        for parent in categories {
+            members.entry(parent).or_default().push(page);
+        }
+    }
+
+    let category = category_title(category, category_namespace);
+    let mut visited = HashSet::from([&category]);
+    let mut queue = VecDeque::from([(&category, 0)]);
+    let mut articles = BTreeSet::new();
+
+    while let Some((current, level)) = queue.pop_front() {
+        for member in members.get(current).into_iter().flatten() {
+            if !member.starts_with(CATEGORY_PREFIX) {
+                articles.insert(member.to_string());
+            } else if depth.is_none_or(|depth| level < depth) && visited.insert(member) {
+                queue.push_back((member, level + 1));
+            }
+        }
+    }
+
+    articles
+}
+
+/// Lists the articles below a category, one title per line.
+pub fn category_tree(category_file_path: PathBuf, category: &str, category_namespace: &str, depth: Option<usize>, output_file_path: Option<PathBuf>) -> Result<()> {
+    let category_graph = load_network(category_file_path)?;
+    let articles = articles_below(&category_graph, category, category_namespace, depth);
+
+    match output_file_path {
+        Some(output_file_path) => {
+            let mut writer = BufWriter::new(File::create(output_file_path)?);
+
+            for article in &articles {
+                writeln!(writer, "{article}")?;
+            }
+
+            writer.flush()?;
+            println!("found {} articles", articles.len());
+        },
+        None => {
+            for article in &articles {
+                println!("{article}");
+            }
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{BTreeSet, HashMap};
+
+    use super::articles_below;
+
+    #[test]
+    fn test_articles_below_with_cycle() {
+        let category_graph = HashMap::from([
+            ("Berlin".to_owned(), vec!["Category:Cities in Germany".to_owned()]),
+            ("Paris".to_owned(), vec!["Category:Cities".to_owned()]),
+            ("Category:Cities in Germany".to_owned(), vec!["Category:Cities".to_owned()]),
+            ("Category:Cities".to_owned(), vec!["Category:Cities in Germany".to_owned()])
+        ]);
+
+        assert_eq!(articles_below(&category_graph, "Cities", "Category", Some(0)), BTreeSet::from(["Paris".to_owned()]));
+        assert_eq!(articles_below(&category_graph, "Cities", "Category", None), BTreeSet::from(["Berlin".to_owned(), "Paris".to_owned()]));
+        assert_eq!(articles_below(&category_graph, "Kategorie:Cities", "Kategorie", Some(0)), BTreeSet::from(["Paris".to_owned()]));
+        assert!(articles_below(&category_graph, "Kategorie:Cities", "Category", Some(0)).is_empty());
+    }
+}
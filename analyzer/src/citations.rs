@@ -0,0 +1,61 @@
+use std::{fs::File, io::{stdout, BufWriter, Result, Write}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{common::{find_entries, process_dump, read_from}, network_generator::{wiki_text::{is_redirect, parse_citations, parse_text, Citation}, wiki_xml_dump::{Namespaces, WikiPage}}};
+
+#[derive(Serialize, Debug)]
+struct CitationRecord<'a> {
+    article: &'a str,
+    #[serde(flatten)]
+    citation: &'a Citation
+}
+
+/// Writes one JSON object with the title of the article and the fields of the citation per line.
+/// The title of the article is called `article` to keep it apart from the title of the citation.
+fn write_citations<W: Write>(writer: &mut W, title: &str, citations: &[Citation]) -> Result<()> {
+    for citation in citations {
+        serde_json::to_writer(&mut *writer, &CitationRecord { article: title, citation })?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+pub fn citations(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String) -> Result<()> {
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                let citations = parse_citations(page.text.as_deref().unwrap_or_default());
+                return write_citations(&mut stdout(), &page.title, &citations);
+            }
+        }
+    }
+
+    println!("No article with title {title} found!");
+
+    Ok(())
+}
+
+pub fn all_citations(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, output_file_path: PathBuf) -> Result<()> {
+    let number_of_threads = 4;
+
+    let process = |page: WikiPage| {
+        if page.namespace_id != Namespaces::MAIN || parse_text(&page).is_none_or(|links| is_redirect(&links)) {
+            return None;
+        }
+
+        let citations = parse_citations(page.text.as_deref().unwrap_or_default());
+        Some((page.title, citations))
+    };
+
+    process_dump(xml_dump_path, xml_dump_index_path, number_of_threads, process, |results| {
+        let mut writer = BufWriter::new(File::create(output_file_path)?);
+
+        for (title, citations) in results {
+            write_citations(&mut writer, &title, &citations)?;
+        }
+
+        writer.flush()
+    })
+}
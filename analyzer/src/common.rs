@@ -1,9 +1,9 @@
-use std::{fs::File, io::{BufRead, BufReader, Read, Result, Seek, SeekFrom}, path::PathBuf};
+use std::{collections::HashMap, fs::File, io::{BufRead, BufReader, Read, Result, Seek, SeekFrom}, path::PathBuf, sync::mpsc::{channel, Receiver}, thread};
 
 use bzip2::bufread::MultiBzDecoder;
 use quick_xml::Reader;
 
-use crate::network_generator::wiki_xml_dump::{read_index, WikiIndexEntry, WikiXmlDump};
+use crate::{network_generator::wiki_xml_dump::{blocks, read_index, Namespaces, WikiIndexEntry, WikiPage, WikiRevisions, WikiXmlDump}, progress::{CountingReader, Progress}};
 
 pub fn read_from(xml_dump_path: &PathBuf, block_start: u64) -> Result<WikiXmlDump<impl BufRead>> {
     let mut file = File::open(xml_dump_path)?;
@@ -24,15 +24,36 @@ pub fn read_from_to(xml_dump_path: &PathBuf, block_start: u64, block_end: u64) -
     let mut file = File::open(xml_dump_path)?;
     file.seek(SeekFrom::Start(block_start))?;
 
-    let buf_reader = BufReader::new(file.take(block_end - block_start));
+    Ok(compressed_dump(file.take(block_end - block_start)))
+}
+
+/// Like `read_from_to`, but counts the compressed bytes read in the progress.
+pub fn read_from_to_with_progress<'a>(xml_dump_path: &PathBuf, block_start: u64, block_end: u64, progress: &'a Progress) -> Result<WikiXmlDump<impl BufRead + 'a>> {
+    let mut file = File::open(xml_dump_path)?;
+    file.seek(SeekFrom::Start(block_start))?;
+
+    Ok(compressed_dump(CountingReader::new(file.take(block_end - block_start), progress)))
+}
+
+fn compressed_dump<R: Read>(compressed_reader: R) -> WikiXmlDump<impl BufRead> {
+    let buf_reader = BufReader::new(compressed_reader);
     let bz_decoder = MultiBzDecoder::new(buf_reader);
     let bz_reader = BufReader::new(bz_decoder);
     let mut reader = Reader::from_reader(bz_reader);
     reader.check_end_names(false);
 
-    let xml_dump = WikiXmlDump::new(reader); 
+    WikiXmlDump::new(reader)
+}
 
-    Ok(xml_dump)
+/// Reads all revisions of a history dump from its start.
+pub fn read_revisions(xml_dump_path: &PathBuf) -> Result<WikiRevisions<impl BufRead>> {
+    let buf_reader = BufReader::new(File::open(xml_dump_path)?);
+    let bz_decoder = MultiBzDecoder::new(buf_reader);
+    let bz_reader = BufReader::new(bz_decoder);
+    let mut reader = Reader::from_reader(bz_reader);
+    reader.check_end_names(false);
+
+    Ok(WikiRevisions::new(reader))
 }
 
 pub fn find_entries(xml_dump_index_path: &PathBuf, title: &String) -> Result<Vec<WikiIndexEntry>> {
@@ -49,4 +70,93 @@ pub fn find_entries(xml_dump_index_path: &PathBuf, title: &String) -> Result<Vec
         .collect();
 
     Ok(entries)
-}
\ No newline at end of file
+}
+
+pub fn read_page_ids(xml_dump_index_path: &PathBuf) -> Result<HashMap<String, u64>> {
+    let file = File::open(xml_dump_index_path)?;
+    let file_reader = BufReader::new(file);
+    let bz_decoder = MultiBzDecoder::new(file_reader);
+
+    let bz_reader = BufReader::new(bz_decoder);
+
+    let wiki_index = read_index(bz_reader)?;
+
+    Ok(wiki_index.into_iter()
+        .map(|entry| (entry.title, entry.id))
+        .collect())
+}
+
+/// The namespaces listed in the siteinfo at the start of the dump.
+pub fn read_namespaces(xml_dump_path: &PathBuf) -> Result<Namespaces> {
+    Ok(read_from(xml_dump_path, 0)?.read_namespaces())
+}
+
+/// Splits the dump into at most `number_of_parts` ranges of whole bzip2 blocks.
+pub fn split_dump(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, number_of_parts: usize) -> Result<Vec<(u64, u64)>> {
+    let file = File::open(xml_dump_index_path)?;
+    let file_reader = BufReader::new(file);
+    let bz_decoder = MultiBzDecoder::new(file_reader);
+
+    let bz_reader = BufReader::new(bz_decoder);
+
+    let start_positions = blocks(read_index(bz_reader)?);
+    let part_size = start_positions.len() / number_of_parts + 1;
+    let file_size = File::open(xml_dump_path)?.metadata()?.len();
+
+    let mut boundaries = start_positions.into_iter()
+        .step_by(part_size)
+        .collect::<Vec<u64>>();
+
+    // the first part also has to read the header before the first indexed block
+    if let Some(first) = boundaries.first_mut() {
+        *first = 0;
+    }
+
+    boundaries.push(file_size + 1);
+
+    Ok(boundaries.windows(2)
+        .map(|range| (range[0], range[1]))
+        .collect())
+}
+
+/// Reads the whole dump with `number_of_threads` threads and calls `process` for every page. The
+/// results are handed to `consume` on the calling thread as soon as they are available. A part of
+/// the dump which can't be read ends its thread and is returned as error after `consume`.
+pub fn process_dump<T, P, C, R>(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, number_of_threads: usize, process: P, consume: C) -> Result<R>
+    where T: Send, P: Fn(WikiPage) -> Option<T> + Sync, C: FnOnce(Receiver<T>) -> Result<R> {
+    let parts = split_dump(xml_dump_path, xml_dump_index_path, number_of_threads)?;
+    let (sender, receiver) = channel();
+
+    thread::scope(|scope| {
+        let handles = parts.into_iter().enumerate().map(|(thread_number, (block_start, block_end))| {
+            let sender = sender.clone();
+            let process = &process;
+
+            scope.spawn(move || -> Result<()> {
+                println!("starting decoding thread {thread_number}");
+
+                for page in read_from_to(xml_dump_path, block_start, block_end)? {
+                    if let Some(result) = process(page) {
+                        if sender.send(result).is_err() {
+                            break;
+                        }
+                    }
+                }
+
+                println!("finished decoding thread {thread_number}");
+
+                Ok(())
+            })
+        }).collect::<Vec<_>>();
+
+        drop(sender);
+
+        let result = consume(receiver);
+
+        for handle in handles {
+            handle.join().unwrap()?;
+        }
+
+        result
+    })
+}
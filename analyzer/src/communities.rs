@@ -0,0 +1,94 @@
+use std::{fs::File, io::{BufWriter, Result, Write}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{analyze::load_network, statistics::{community::louvain, indexed_network::IndexedNetwork, page_rank::{page_rank, DAMPING_FACTOR}}};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CommunityStatistics {
+    seed: u64,
+    resolution: f64,
+    modularity: f64,
+    number_of_communities: usize,
+    communities: Vec<Community>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct Community {
+    id: usize,
+    size: usize,
+    top_members: Vec<RankedMember>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct RankedMember {
+    title: String,
+    page_rank: f64
+}
+
+pub fn communities(network_file_path: PathBuf, assignment_file_path: PathBuf, statistics_file_path: PathBuf, seed: u64, resolution: f64, number_of_top_members: usize) -> Result<()> {
+    let network = load_network(network_file_path)?;
+    let indexed_network = IndexedNetwork::new(&network);
+
+    println!("detecting communities");
+    let partition = louvain(&indexed_network, seed, resolution);
+    println!("found {} communities with modularity {}", partition.number_of_communities, partition.modularity);
+
+    let ranks = page_rank(&indexed_network, DAMPING_FACTOR);
+
+    let mut members = vec![Vec::new(); partition.number_of_communities];
+    for (node, community) in partition.communities.iter().enumerate() {
+        members[*community].push(node);
+    }
+
+    // communities are numbered by decreasing size, ties are broken by their first member
+    let mut order = (0..partition.number_of_communities).collect::<Vec<usize>>();
+    order.sort_by_key(|community| (std::cmp::Reverse(members[*community].len()), members[*community][0]));
+
+    let mut new_ids = vec![0; partition.number_of_communities];
+    for (new_id, community) in order.iter().enumerate() {
+        new_ids[*community] = new_id;
+    }
+
+    let mut assignment_writer = BufWriter::new(File::create(assignment_file_path)?);
+    for (node, community) in partition.communities.iter().enumerate() {
+        assignment_writer.write_all(indexed_network.nodes[node].as_bytes())?;
+        assignment_writer.write_all(b";")?;
+        assignment_writer.write_all(new_ids[*community].to_string().as_bytes())?;
+        assignment_writer.write_all(b"\n")?;
+    }
+    assignment_writer.flush()?;
+
+    let communities = order.iter()
+        .enumerate()
+        .map(|(id, community)| {
+            let mut community_members = members[*community].clone();
+            community_members.sort_by(|a, b| ranks[*b].total_cmp(&ranks[*a]));
+
+            Community {
+                id,
+                size: community_members.len(),
+                top_members: community_members.iter()
+                    .take(number_of_top_members)
+                    .map(|node| RankedMember { title: indexed_network.nodes[*node].to_owned(), page_rank: ranks[*node] })
+                    .collect()
+            }
+        })
+        .collect();
+
+    let statistics = CommunityStatistics {
+        seed,
+        resolution,
+        modularity: partition.modularity,
+        number_of_communities: partition.number_of_communities,
+        communities
+    };
+
+    let statistics_writer = BufWriter::new(File::create(statistics_file_path)?);
+    serde_json::to_writer(statistics_writer, &statistics)?;
+
+    Ok(())
+}
@@ -0,0 +1,251 @@
+use std::{collections::{BTreeSet, HashMap}, fs::File, io::{BufRead, BufReader, BufWriter, Result}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{analyze::load_network, statistics::top_k::{top_k, ScoredNode}};
+
+#[derive(Serialize, Debug, Default, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ChurnStatistics {
+    number_of_articles_before: usize,
+    number_of_articles_after: usize,
+    number_of_added_articles: usize,
+    number_of_removed_articles: usize,
+    number_of_renamed_articles: usize,
+    /// Articles which were merged into another article, counted once per new article.
+    number_of_merged_articles: usize,
+    /// Articles in both networks whose links changed.
+    number_of_changed_articles: usize,
+    number_of_links_before: usize,
+    number_of_links_after: usize,
+    number_of_added_links: usize,
+    number_of_removed_links: usize,
+    /// Added and removed links relative to the links before.
+    link_churn: f64
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Rename {
+    from: String,
+    to: String
+}
+
+/// Several old articles which all redirect to the same new article.
+#[derive(Serialize, Debug, PartialEq)]
+pub struct Merge {
+    from: Vec<String>,
+    to: String
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ArticleDiff {
+    title: String,
+    added_links: Vec<String>,
+    removed_links: Vec<String>
+}
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NetworkDiff {
+    statistics: ChurnStatistics,
+    added_articles: Vec<String>,
+    removed_articles: Vec<String>,
+    renamed_articles: Vec<Rename>,
+    merged_articles: Vec<Merge>,
+    /// The articles with the most added and removed links.
+    most_changed_articles: Vec<ScoredNode<usize>>,
+    changed_articles: Vec<ArticleDiff>
+}
+
+/// Compares two snapshots of a network. Articles which disappeared while a redirect from their
+/// title to a new article appeared count as renamed, and links to their old title in either
+/// snapshot are compared as links to the new title. If several articles redirect to the same new
+/// article they count as merged, and the new article is compared with the links of all of them.
+pub fn diff(old_network_file_path: PathBuf, new_network_file_path: PathBuf, output_path: PathBuf, redirects_file_path: Option<PathBuf>, number_of_top_nodes: usize) -> Result<()> {
+    println!("loading the networks");
+    let old_network = load_network(old_network_file_path)?;
+    let new_network = load_network(new_network_file_path)?;
+
+    let redirects = match redirects_file_path {
+        Some(redirects_file_path) => load_redirects(redirects_file_path)?,
+        None => HashMap::new()
+    };
+
+    println!("comparing the networks");
+    let network_diff = compare_networks(&old_network, &new_network, &redirects, number_of_top_nodes);
+    let writer = BufWriter::new(File::create(output_path)?);
+    serde_json::to_writer(writer, &network_diff)?;
+
+    Ok(())
+}
+
+fn load_redirects(redirects_file_path: PathBuf) -> Result<HashMap<String, String>> {
+    let mut redirects = HashMap::new();
+
+    for line in BufReader::new(File::open(redirects_file_path)?).lines() {
+        if let Some((link, target)) = line?.split_once(';') {
+            redirects.insert(link.to_owned(), target.to_owned());
+        }
+    }
+
+    Ok(redirects)
+}
+
+fn compare_networks(old_network: &HashMap<String, Vec<String>>, new_network: &HashMap<String, Vec<String>>, redirects: &HashMap<String, String>, number_of_top_nodes: usize) -> NetworkDiff {
+    let renames = old_network.keys()
+        .filter(|title| !new_network.contains_key(*title))
+        .filter_map(|title| Some((title, redirects.get(title)?)))
+        .filter(|(_, target)| new_network.contains_key(*target) && !old_network.contains_key(*target))
+        .collect::<HashMap<&String, &String>>();
+    let renamed = |title: &String| renames.get(title).copied().unwrap_or(title).to_owned();
+
+    let mut sources = HashMap::<&String, Vec<&String>>::new();
+
+    for (from, to) in &renames {
+        sources.entry(*to).or_default().push(*from);
+    }
+
+    let mut removed_articles = old_network.keys()
+        .filter(|title| !new_network.contains_key(*title) && !renames.contains_key(title))
+        .cloned()
+        .collect::<Vec<String>>();
+    let mut added_articles = new_network.keys()
+        .filter(|title| !old_network.contains_key(*title) && !sources.contains_key(title))
+        .cloned()
+        .collect::<Vec<String>>();
+    removed_articles.sort();
+    added_articles.sort();
+
+    let mut statistics = ChurnStatistics {
+        number_of_articles_before: old_network.len(),
+        number_of_articles_after: new_network.len(),
+        number_of_added_articles: added_articles.len(),
+        number_of_removed_articles: removed_articles.len(),
+        number_of_renamed_articles: sources.values().filter(|from| from.len() == 1).count(),
+        number_of_merged_articles: sources.values().filter(|from| from.len() > 1).count(),
+        number_of_links_before: old_network.values().map(|links| links.len()).sum(),
+        number_of_links_after: new_network.values().map(|links| links.len()).sum(),
+        ..ChurnStatistics::default()
+    };
+
+    // merged articles are compared once with the links of all the articles merged into them
+    let mut old_links_by_title = HashMap::<String, BTreeSet<String>>::new();
+
+    for (old_title, old_links) in old_network {
+        old_links_by_title.entry(renamed(old_title)).or_default().extend(old_links.iter().map(renamed));
+    }
+
+    let mut changed_articles = Vec::new();
+
+    for (title, old_links) in old_links_by_title {
+        let Some(new_links) = new_network.get(&title) else {
+            continue;
+        };
+
+        let new_links = new_links.iter().map(renamed).collect::<BTreeSet<String>>();
+
+        let added_links = new_links.difference(&old_links).cloned().collect::<Vec<String>>();
+        let removed_links = old_links.difference(&new_links).cloned().collect::<Vec<String>>();
+
+        if !added_links.is_empty() || !removed_links.is_empty() {
+            statistics.number_of_added_links += added_links.len();
+            statistics.number_of_removed_links += removed_links.len();
+            changed_articles.push(ArticleDiff { title, added_links, removed_links });
+        }
+    }
+
+    changed_articles.sort_by(|first, second| first.title.cmp(&second.title));
+
+    statistics.number_of_changed_articles = changed_articles.len();
+    statistics.link_churn = (statistics.number_of_added_links + statistics.number_of_removed_links) as f64
+        / statistics.number_of_links_before.max(1) as f64;
+
+    let mut renamed_articles = Vec::new();
+    let mut merged_articles = Vec::new();
+
+    for (to, mut from) in sources {
+        if let [from] = from[..] {
+            renamed_articles.push(Rename { from: from.to_owned(), to: to.to_owned() });
+        } else {
+            from.sort();
+            merged_articles.push(Merge { from: from.into_iter().cloned().collect(), to: to.to_owned() });
+        }
+    }
+
+    renamed_articles.sort_by(|first, second| first.from.cmp(&second.from));
+    merged_articles.sort_by(|first, second| first.to.cmp(&second.to));
+
+    NetworkDiff {
+        statistics,
+        added_articles,
+        removed_articles,
+        renamed_articles,
+        merged_articles,
+        most_changed_articles: top_k(changed_articles.iter()
+            .map(|article| (article.title.as_str(), article.added_links.len() + article.removed_links.len())), number_of_top_nodes),
+        changed_articles
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use super::{compare_networks, ArticleDiff, Merge, Rename};
+
+    fn network(lines: &[&str]) -> HashMap<String, Vec<String>> {
+        lines.iter()
+            .map(|line| {
+                let mut entries = line.split("; ").map(|entry| entry.to_owned());
+                (entries.next().unwrap(), entries.collect())
+            })
+            .collect()
+    }
+
+    #[test]
+    fn test_compare_networks() {
+        let old_network = network(&["Berlin; Germany; Bonn", "Germany; Berlin; West Germany", "West Germany; Bonn", "Bonn"]);
+        let new_network = network(&["Berlin; Germany; Potsdam", "Germany; Berlin; Federal Republic of Germany", "Federal Republic of Germany; Bonn", "Potsdam; Berlin"]);
+        let redirects = HashMap::from([("West Germany".to_owned(), "Federal Republic of Germany".to_owned())]);
+
+        let network_diff = compare_networks(&old_network, &new_network, &redirects, 10);
+
+        assert_eq!(network_diff.added_articles, vec!["Potsdam"]);
+        assert_eq!(network_diff.removed_articles, vec!["Bonn"]);
+        assert_eq!(network_diff.renamed_articles, vec![Rename { from: "West Germany".to_owned(), to: "Federal Republic of Germany".to_owned() }]);
+        assert_eq!(network_diff.changed_articles, vec![ArticleDiff {
+            title: "Berlin".to_owned(),
+            added_links: vec!["Potsdam".to_owned()],
+            removed_links: vec!["Bonn".to_owned()]
+        }]);
+        assert_eq!(network_diff.statistics.number_of_added_links, 1);
+        assert_eq!(network_diff.statistics.number_of_removed_links, 1);
+    }
+
+    #[test]
+    fn test_compare_networks_with_merge() {
+        let old_network = network(&["East Berlin; Spree; Berlin Wall", "West Berlin; Berlin Wall; Bonn", "Berlin Wall; East Berlin; West Berlin", "Spree", "Bonn"]);
+        let new_network = network(&["Berlin; Spree; Berlin Wall; Potsdam", "Berlin Wall; Berlin", "Spree", "Bonn", "Potsdam"]);
+        let redirects = HashMap::from([
+            ("East Berlin".to_owned(), "Berlin".to_owned()),
+            ("West Berlin".to_owned(), "Berlin".to_owned())
+        ]);
+
+        let network_diff = compare_networks(&old_network, &new_network, &redirects, 10);
+
+        assert!(network_diff.renamed_articles.is_empty());
+        assert_eq!(network_diff.merged_articles, vec![Merge { from: vec!["East Berlin".to_owned(), "West Berlin".to_owned()], to: "Berlin".to_owned() }]);
+        assert_eq!(network_diff.added_articles, vec!["Potsdam"]);
+        assert_eq!(network_diff.changed_articles, vec![ArticleDiff {
+            title: "Berlin".to_owned(),
+            added_links: vec!["Potsdam".to_owned()],
+            removed_links: vec!["Bonn".to_owned()]
+        }]);
+        assert_eq!(network_diff.statistics.number_of_renamed_articles, 0);
+        assert_eq!(network_diff.statistics.number_of_merged_articles, 1);
+        assert_eq!(network_diff.statistics.number_of_added_links, 1);
+        assert_eq!(network_diff.statistics.number_of_removed_links, 1);
+        assert_eq!(network_diff.most_changed_articles.len(), 1);
+    }
+}
@@ -0,0 +1,132 @@
+use std::{io::Result, path::PathBuf};
+
+use crate::{analyze::load_network, common::read_page_ids, node_attributes::{load_node_attributes, NodeAttributes, NodeRecord}, graph_format::{write_network, AttributeValues, GraphFormat, NodeAttribute}, statistics::{hits::hits, indexed_network::IndexedNetwork, k_core::core_numbers, page_rank::{page_rank, DAMPING_FACTOR}}};
+
+pub const NODE_ATTRIBUTE_NAMES: [&str; 6] = ["in-degree", "out-degree", "page-rank", "hub", "authority", "core-number"];
+
+pub fn export(network_file_path: PathBuf, output_path: PathBuf, format: GraphFormat, attribute_names: Vec<String>, xml_dump_index_path: Option<PathBuf>, node_file_path: Option<PathBuf>) -> Result<()> {
+    let network = load_network(network_file_path)?;
+    let indexed_network = IndexedNetwork::new(&network);
+
+    let mut attributes = Vec::new();
+
+    if let Some(xml_dump_index_path) = xml_dump_index_path {
+        println!("reading page ids");
+        attributes.push(page_id_attribute(&indexed_network, &xml_dump_index_path)?);
+    }
+
+    if let Some(node_file_path) = node_file_path {
+        println!("reading node attributes");
+        attributes.extend(node_file_attributes(&indexed_network, &load_node_attributes(node_file_path)?));
+    }
+
+    attributes.extend(computed_attributes(&indexed_network, &attribute_names));
+
+    println!("writing network");
+    write_network(format, &indexed_network, &attributes, output_path)
+}
+
+pub fn page_id_attribute(network: &IndexedNetwork, xml_dump_index_path: &PathBuf) -> Result<NodeAttribute> {
+    let page_ids = read_page_ids(xml_dump_index_path)?;
+
+    Ok(NodeAttribute {
+        name: "pageId".to_owned(),
+        values: AttributeValues::Integer(network.nodes.iter()
+            .map(|title| page_ids.get(*title).map(|id| *id as i64))
+            .collect())
+    })
+}
+
+/// The attributes saved with the network, missing for nodes without a record like red links.
+pub fn node_file_attributes(network: &IndexedNetwork, nodes: &NodeAttributes) -> Vec<NodeAttribute> {
+    let integers = |value: fn(&NodeRecord) -> Option<usize>| AttributeValues::Integer(network.nodes.iter()
+        .map(|title| nodes.get(*title).and_then(value).map(|value| value as i64))
+        .collect());
+    let booleans = |value: fn(&NodeRecord) -> Option<bool>| AttributeValues::Boolean(network.nodes.iter()
+        .map(|title| nodes.get(*title).and_then(value))
+        .collect());
+
+    vec![
+        NodeAttribute { name: "length".to_owned(), values: integers(|node| node.length) },
+        NodeAttribute { name: "links".to_owned(), values: integers(|node| node.links) },
+        NodeAttribute { name: "templates".to_owned(), values: integers(|node| node.templates) },
+        NodeAttribute { name: "citations".to_owned(), values: integers(|node| node.citations) },
+        NodeAttribute { name: "stub".to_owned(), values: booleans(|node| node.stub) },
+        NodeAttribute { name: "list".to_owned(), values: booleans(|node| node.list) },
+        NodeAttribute { name: "disambiguation".to_owned(), values: booleans(|node| node.disambiguation) }
+    ]
+}
+
+/// Computes the per-node metrics named in `attribute_names`, see `NODE_ATTRIBUTE_NAMES`.
+pub fn computed_attributes(network: &IndexedNetwork, attribute_names: &[String]) -> Vec<NodeAttribute> {
+    let requested = |name: &str| attribute_names.iter().any(|attribute_name| attribute_name == name);
+    let integers = |values: Vec<usize>| AttributeValues::Integer(values.into_iter().map(|value| Some(value as i64)).collect());
+    let floats = |values: Vec<f64>| AttributeValues::Float(values.into_iter().map(Some).collect());
+
+    let mut attributes = Vec::new();
+
+    if requested("in-degree") {
+        let mut in_degrees = vec![0; network.len()];
+        for target in network.out_edges.iter().flatten() {
+            in_degrees[*target] += 1;
+        }
+
+        attributes.push(NodeAttribute { name: "inDegree".to_owned(), values: integers(in_degrees) });
+    }
+
+    if requested("out-degree") {
+        let out_degrees = network.out_edges.iter().map(|targets| targets.len()).collect();
+        attributes.push(NodeAttribute { name: "outDegree".to_owned(), values: integers(out_degrees) });
+    }
+
+    if requested("page-rank") {
+        attributes.push(NodeAttribute { name: "pageRank".to_owned(), values: floats(page_rank(network, DAMPING_FACTOR)) });
+    }
+
+    if requested("hub") || requested("authority") {
+        let (hubs, authorities) = hits(network);
+
+        if requested("hub") {
+            attributes.push(NodeAttribute { name: "hub".to_owned(), values: floats(hubs) });
+        }
+
+        if requested("authority") {
+            attributes.push(NodeAttribute { name: "authority".to_owned(), values: floats(authorities) });
+        }
+    }
+
+    if requested("core-number") {
+        attributes.push(NodeAttribute { name: "coreNumber".to_owned(), values: integers(core_numbers(network)) });
+    }
+
+    attributes
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{graph_format::AttributeValues, node_attributes::NodeRecord, statistics::indexed_network::IndexedNetwork};
+
+    use super::node_file_attributes;
+
+    #[test]
+    fn test_node_file_attributes() {
+        let network = HashMap::from([
+            ("Berlin".to_string(), vec!["Red link".to_string()]),
+            ("Red link".to_string(), vec![])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+        let nodes = HashMap::from([
+            ("Berlin".to_string(), NodeRecord { title: "Berlin".to_string(), length: Some(2000), links: Some(1), stub: Some(false), ..NodeRecord::default() })
+        ]);
+
+        let attributes = node_file_attributes(&indexed_network, &nodes);
+        let attribute = |name: &str| &attributes.iter().find(|attribute| attribute.name == name).unwrap().values;
+
+        assert!(matches!(attribute("length"), AttributeValues::Integer(values) if *values == vec![Some(2000), None]));
+        assert!(matches!(attribute("links"), AttributeValues::Integer(values) if *values == vec![Some(1), None]));
+        assert!(matches!(attribute("citations"), AttributeValues::Integer(values) if *values == vec![None, None]));
+        assert!(matches!(attribute("stub"), AttributeValues::Boolean(values) if *values == vec![Some(false), None]));
+    }
+}
@@ -0,0 +1,126 @@
+use std::{collections::{BTreeSet, HashMap}, fs::File, io::{BufWriter, Result, Write}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{common::{find_entries, process_dump, read_from}, network_generator::{wiki_text::{external_links, is_redirect, normalize_url, parse_text, url_domain}, wiki_xml_dump::{Namespaces, WikiPage}}};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DomainStatistics {
+    number_of_links: usize,
+    number_of_articles_with_links: usize,
+    number_of_domains: usize,
+    domains: Vec<DomainCount>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+struct DomainCount {
+    domain: String,
+    links: usize,
+    articles: usize
+}
+
+/// The normalized external links of a page.
+fn normalized_links(page: &WikiPage) -> Vec<String> {
+    page.text.as_deref()
+        .map(external_links)
+        .unwrap_or_default()
+        .into_iter()
+        .filter_map(normalize_url)
+        .collect()
+}
+
+/// Prints the normalized external links of the article with the given title.
+pub fn article_external_links(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String) -> Result<()> {
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                for link in normalized_links(&page) {
+                    println!("{link}");
+                }
+
+                return Ok(());
+            }
+        }
+    }
+
+    println!("No article with title {title} found!");
+
+    Ok(())
+}
+
+/// Counts the external links to every domain in the dump. If `graph_file_path` is given, the
+/// domains linked by every article are saved in the network format.
+pub fn domains(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, statistics_file_path: PathBuf, graph_file_path: Option<PathBuf>) -> Result<()> {
+    let number_of_threads = 4;
+
+    let process = |page: WikiPage| {
+        if page.namespace_id != Namespaces::MAIN || parse_text(&page).is_none_or(|links| is_redirect(&links)) {
+            return None;
+        }
+
+        let links = normalized_links(&page);
+        (!links.is_empty()).then_some((page.title, links))
+    };
+
+    let statistics = process_dump(xml_dump_path, xml_dump_index_path, number_of_threads, process, |results| {
+        let mut graph_writer = graph_file_path.map(File::create)
+            .transpose()?
+            .map(BufWriter::new);
+
+        let mut counts: HashMap<String, (usize, usize)> = HashMap::new();
+        let mut number_of_links = 0;
+        let mut number_of_articles_with_links = 0;
+
+        for (title, links) in results {
+            let article_domains = links.iter()
+                .filter_map(|link| url_domain(link))
+                .collect::<Vec<&str>>();
+
+            for domain in &article_domains {
+                counts.entry(domain.to_string()).or_default().0 += 1;
+            }
+
+            let article_domains = article_domains.into_iter().collect::<BTreeSet<&str>>();
+
+            for domain in &article_domains {
+                counts.entry(domain.to_string()).or_default().1 += 1;
+            }
+
+            if let Some(graph_writer) = graph_writer.as_mut() {
+                write!(graph_writer, "{title}")?;
+
+                for domain in &article_domains {
+                    write!(graph_writer, "; {domain}")?;
+                }
+
+                writeln!(graph_writer)?;
+            }
+
+            number_of_links += links.len();
+            number_of_articles_with_links += 1;
+        }
+
+        if let Some(graph_writer) = graph_writer.as_mut() {
+            graph_writer.flush()?;
+        }
+
+        let mut domains = counts.into_iter()
+            .map(|(domain, (links, articles))| DomainCount { domain, links, articles })
+            .collect::<Vec<DomainCount>>();
+        domains.sort_by(|a, b| b.links.cmp(&a.links).then_with(|| a.domain.cmp(&b.domain)));
+
+        Ok(DomainStatistics {
+            number_of_links,
+            number_of_articles_with_links,
+            number_of_domains: domains.len(),
+            domains
+        })
+    })?;
+
+    let statistics_writer = BufWriter::new(File::create(statistics_file_path)?);
+    serde_json::to_writer(statistics_writer, &statistics)?;
+
+    Ok(())
+}
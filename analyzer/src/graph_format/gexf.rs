@@ -0,0 +1,106 @@
+use std::io::{Result, Write};
+
+use quick_xml::escape::escape;
+
+use crate::statistics::indexed_network::IndexedNetwork;
+
+use super::{AttributeValues, NodeAttribute};
+
+pub fn write<W: Write>(network: &IndexedNetwork, attributes: &[NodeAttribute], writer: &mut W) -> Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<gexf xmlns="http://gexf.net/1.3" version="1.3">"#)?;
+    writeln!(writer, r#"  <graph mode="static" defaultedgetype="directed">"#)?;
+
+    if !attributes.is_empty() {
+        writeln!(writer, r#"    <attributes class="node">"#)?;
+
+        for (index, attribute) in attributes.iter().enumerate() {
+            let attribute_type = match attribute.values {
+                AttributeValues::Integer(_) => "long",
+                AttributeValues::Float(_) => "double",
+                AttributeValues::Boolean(_) => "boolean"
+            };
+
+            writeln!(writer, r#"      <attribute id="{index}" title="{}" type="{attribute_type}"/>"#, escape(&attribute.name))?;
+        }
+
+        writeln!(writer, "    </attributes>")?;
+    }
+
+    writeln!(writer, "    <nodes>")?;
+
+    for (node, title) in network.nodes.iter().enumerate() {
+        write!(writer, r#"      <node id="{node}" label="{}">"#, escape(title.as_str()))?;
+
+        if !attributes.is_empty() {
+            write!(writer, "<attvalues>")?;
+
+            for (index, attribute) in attributes.iter().enumerate() {
+                if let Some(value) = attribute.value_text(node) {
+                    write!(writer, r#"<attvalue for="{index}" value="{value}"/>"#)?;
+                }
+            }
+
+            write!(writer, "</attvalues>")?;
+        }
+
+        writeln!(writer, "</node>")?;
+    }
+
+    writeln!(writer, "    </nodes>")?;
+    writeln!(writer, "    <edges>")?;
+
+    let mut edge_id = 0;
+    for (source, targets) in network.out_edges.iter().enumerate() {
+        for target in targets {
+            writeln!(writer, r#"      <edge id="{edge_id}" source="{source}" target="{target}"/>"#)?;
+            edge_id += 1;
+        }
+    }
+
+    writeln!(writer, "    </edges>")?;
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</gexf>")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{graph_format::{AttributeValues, NodeAttribute}, statistics::indexed_network::IndexedNetwork};
+
+    use super::write;
+
+    #[test]
+    fn test_write_gexf() {
+        let network = HashMap::from([
+            ("AT&T".to_string(), vec!["<b>".to_string()]),
+            ("<b>".to_string(), vec![]),
+            ("Say \"Hi\"".to_string(), vec!["AT&T".to_string()])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+        let attributes = [NodeAttribute { name: "stub".to_string(), values: AttributeValues::Boolean(vec![Some(true), None, Some(false)]) }];
+
+        let mut output = Vec::new();
+        write(&indexed_network, &attributes, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), r#"<?xml version="1.0" encoding="UTF-8"?>
+<gexf xmlns="http://gexf.net/1.3" version="1.3">
+  <graph mode="static" defaultedgetype="directed">
+    <attributes class="node">
+      <attribute id="0" title="stub" type="boolean"/>
+    </attributes>
+    <nodes>
+      <node id="0" label="&lt;b&gt;"><attvalues><attvalue for="0" value="true"/></attvalues></node>
+      <node id="1" label="AT&amp;T"><attvalues></attvalues></node>
+      <node id="2" label="Say &quot;Hi&quot;"><attvalues><attvalue for="0" value="false"/></attvalues></node>
+    </nodes>
+    <edges>
+      <edge id="0" source="1" target="0"/>
+      <edge id="1" source="2" target="1"/>
+    </edges>
+  </graph>
+</gexf>
+"#);
+    }
+}
@@ -0,0 +1,83 @@
+use std::io::{Result, Write};
+
+use quick_xml::escape::escape;
+
+use crate::statistics::indexed_network::IndexedNetwork;
+
+use super::{AttributeValues, NodeAttribute};
+
+pub fn write<W: Write>(network: &IndexedNetwork, attributes: &[NodeAttribute], writer: &mut W) -> Result<()> {
+    writeln!(writer, r#"<?xml version="1.0" encoding="UTF-8"?>"#)?;
+    writeln!(writer, r#"<graphml xmlns="http://graphml.graphdrawing.org/xmlns">"#)?;
+    writeln!(writer, r#"  <key id="label" for="node" attr.name="label" attr.type="string"/>"#)?;
+
+    for (index, attribute) in attributes.iter().enumerate() {
+        let attribute_type = match attribute.values {
+            AttributeValues::Integer(_) => "long",
+            AttributeValues::Float(_) => "double",
+            AttributeValues::Boolean(_) => "boolean"
+        };
+
+        writeln!(writer, r#"  <key id="d{index}" for="node" attr.name="{}" attr.type="{attribute_type}"/>"#, escape(&attribute.name))?;
+    }
+
+    writeln!(writer, r#"  <graph id="G" edgedefault="directed">"#)?;
+
+    for (node, title) in network.nodes.iter().enumerate() {
+        write!(writer, r#"    <node id="n{node}"><data key="label">{}</data>"#, escape(title.as_str()))?;
+
+        for (index, attribute) in attributes.iter().enumerate() {
+            if let Some(value) = attribute.value_text(node) {
+                write!(writer, r#"<data key="d{index}">{value}</data>"#)?;
+            }
+        }
+
+        writeln!(writer, "</node>")?;
+    }
+
+    for (source, targets) in network.out_edges.iter().enumerate() {
+        for target in targets {
+            writeln!(writer, r#"    <edge source="n{source}" target="n{target}"/>"#)?;
+        }
+    }
+
+    writeln!(writer, "  </graph>")?;
+    writeln!(writer, "</graphml>")
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{graph_format::{AttributeValues, NodeAttribute}, statistics::indexed_network::IndexedNetwork};
+
+    use super::write;
+
+    #[test]
+    fn test_write_graphml() {
+        let network = HashMap::from([
+            ("AT&T".to_string(), vec!["<b>".to_string()]),
+            ("<b>".to_string(), vec![]),
+            ("Say \"Hi\"".to_string(), vec!["AT&T".to_string()])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+        let attributes = [NodeAttribute { name: "page id".to_string(), values: AttributeValues::Integer(vec![Some(3), None, Some(1)]) }];
+
+        let mut output = Vec::new();
+        write(&indexed_network, &attributes, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), r#"<?xml version="1.0" encoding="UTF-8"?>
+<graphml xmlns="http://graphml.graphdrawing.org/xmlns">
+  <key id="label" for="node" attr.name="label" attr.type="string"/>
+  <key id="d0" for="node" attr.name="page id" attr.type="long"/>
+  <graph id="G" edgedefault="directed">
+    <node id="n0"><data key="label">&lt;b&gt;</data><data key="d0">3</data></node>
+    <node id="n1"><data key="label">AT&amp;T</data></node>
+    <node id="n2"><data key="label">Say &quot;Hi&quot;</data><data key="d0">1</data></node>
+    <edge source="n1" target="n0"/>
+    <edge source="n2" target="n1"/>
+  </graph>
+</graphml>
+"#);
+    }
+}
@@ -0,0 +1,44 @@
+use std::io::{Result, Write};
+
+use crate::statistics::indexed_network::IndexedNetwork;
+
+/// Writes the adjacency matrix in Matrix Market coordinate format. Row and column `i` belong to the
+/// article in line `i` of the node table.
+pub fn write<W: Write>(network: &IndexedNetwork, writer: &mut W) -> Result<()> {
+    let number_of_edges = network.out_edges.iter().map(|targets| targets.len()).sum::<usize>();
+
+    writeln!(writer, "%%MatrixMarket matrix coordinate pattern general")?;
+    writeln!(writer, "{} {} {}", network.len(), network.len(), number_of_edges)?;
+
+    for (source, targets) in network.out_edges.iter().enumerate() {
+        for target in targets {
+            writeln!(writer, "{} {}", source + 1, target + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::statistics::indexed_network::IndexedNetwork;
+
+    use super::write;
+
+    #[test]
+    fn test_write_matrix_market() {
+        let network = HashMap::from([
+            ("A".to_string(), vec!["B".to_string(), "C".to_string()]),
+            ("B".to_string(), vec![]),
+            ("C".to_string(), vec!["A".to_string()])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        let mut output = Vec::new();
+        write(&indexed_network, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "%%MatrixMarket matrix coordinate pattern general\n3 3 3\n1 2\n1 3\n3 1\n");
+    }
+}
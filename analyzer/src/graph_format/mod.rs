@@ -0,0 +1,132 @@
+use std::{fs::File, io::{BufWriter, Result, Write}, path::{Path, PathBuf}};
+
+use crate::statistics::indexed_network::IndexedNetwork;
+
+pub mod graphml;
+pub mod gexf;
+pub mod pajek;
+pub mod matrix_market;
+#[cfg(feature = "parquet")]
+pub mod parquet;
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum GraphFormat {
+    Network, GraphMl, Gexf, Pajek, MatrixMarket, Parquet
+}
+
+impl GraphFormat {
+    pub const NAMES: [&'static str; 6] = ["network", "graphml", "gexf", "pajek", "matrix-market", "parquet"];
+
+    pub fn from_name(name: &str) -> Option<GraphFormat> {
+        match name {
+            "network" => Some(GraphFormat::Network),
+            "graphml" => Some(GraphFormat::GraphMl),
+            "gexf" => Some(GraphFormat::Gexf),
+            "pajek" => Some(GraphFormat::Pajek),
+            "matrix-market" => Some(GraphFormat::MatrixMarket),
+            "parquet" => Some(GraphFormat::Parquet),
+            _ => None
+        }
+    }
+}
+
+/// Values of one node attribute, in the order of the nodes of the network. Missing values are
+/// `None`, for example page ids of articles which are not in the index.
+pub enum AttributeValues {
+    Integer(Vec<Option<i64>>),
+    Float(Vec<Option<f64>>),
+    Boolean(Vec<Option<bool>>)
+}
+
+pub struct NodeAttribute {
+    pub name: String,
+    pub values: AttributeValues
+}
+
+impl NodeAttribute {
+    pub fn value_text(&self, node: usize) -> Option<String> {
+        match &self.values {
+            AttributeValues::Integer(values) => values[node].map(|value| value.to_string()),
+            AttributeValues::Float(values) => values[node].map(|value| value.to_string()),
+            AttributeValues::Boolean(values) => values[node].map(|value| value.to_string())
+        }
+    }
+}
+
+pub fn write_network(format: GraphFormat, network: &IndexedNetwork, attributes: &[NodeAttribute], path: PathBuf) -> Result<()> {
+    if format == GraphFormat::Parquet {
+        return write_parquet(network, attributes, path);
+    }
+
+    let mut writer = BufWriter::with_capacity(16 * 1024 * 1024, File::create(&path)?);
+
+    match format {
+        GraphFormat::Network => write_semicolon_network(network, &mut writer)?,
+        GraphFormat::GraphMl => graphml::write(network, attributes, &mut writer)?,
+        GraphFormat::Gexf => gexf::write(network, attributes, &mut writer)?,
+        GraphFormat::Pajek => pajek::write(network, &mut writer)?,
+        GraphFormat::MatrixMarket => {
+            matrix_market::write(network, &mut writer)?;
+            write_node_table(network, attributes, &node_table_path(&path, "tsv"))?;
+        },
+        GraphFormat::Parquet => unreachable!()
+    }
+
+    writer.flush()
+}
+
+#[cfg(feature = "parquet")]
+fn write_parquet(network: &IndexedNetwork, attributes: &[NodeAttribute], path: PathBuf) -> Result<()> {
+    let node_table_path = node_table_path(&path, "parquet");
+    parquet::write(network, attributes, &path, &node_table_path)
+}
+
+#[cfg(not(feature = "parquet"))]
+fn write_parquet(_network: &IndexedNetwork, _attributes: &[NodeAttribute], _path: PathBuf) -> Result<()> {
+    Err(std::io::Error::new(std::io::ErrorKind::Unsupported, "wikilytics was built without the parquet feature"))
+}
+
+/// The node table of formats which can only store edges is saved next to the edge file, e.g.
+/// `network.mtx` gets `network.nodes.tsv`.
+pub fn node_table_path(path: &Path, extension: &str) -> PathBuf {
+    path.with_extension(format!("nodes.{extension}"))
+}
+
+/// The format written by the `network` subcommand, one article per line followed by its links.
+fn write_semicolon_network<W: Write>(network: &IndexedNetwork, writer: &mut W) -> Result<()> {
+    for (node, targets) in network.out_edges.iter().enumerate() {
+        writer.write_all(network.nodes[node].as_bytes())?;
+
+        for target in targets {
+            writer.write_all(b"; ")?;
+            writer.write_all(network.nodes[*target].as_bytes())?;
+        }
+
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+/// Tab separated node table with the position of the node in the edge file, starting at one.
+fn write_node_table(network: &IndexedNetwork, attributes: &[NodeAttribute], path: &Path) -> Result<()> {
+    let mut writer = BufWriter::new(File::create(path)?);
+
+    write!(writer, "index\ttitle")?;
+    for attribute in attributes {
+        write!(writer, "\t{}", attribute.name)?;
+    }
+    writeln!(writer)?;
+
+    for (node, title) in network.nodes.iter().enumerate() {
+        write!(writer, "{}\t{}", node + 1, title)?;
+
+        for attribute in attributes {
+            write!(writer, "\t{}", attribute.value_text(node).unwrap_or_default())?;
+        }
+
+        writeln!(writer)?;
+    }
+
+    writer.flush()
+}
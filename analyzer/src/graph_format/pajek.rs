@@ -0,0 +1,47 @@
+use std::io::{Result, Write};
+
+use crate::statistics::indexed_network::IndexedNetwork;
+
+/// Writes the network as Pajek `.net` file. Pajek has no node attributes besides the label.
+pub fn write<W: Write>(network: &IndexedNetwork, writer: &mut W) -> Result<()> {
+    writeln!(writer, "*Vertices {}", network.len())?;
+
+    for (node, title) in network.nodes.iter().enumerate() {
+        // labels are quoted and can not contain quotes themselves
+        writeln!(writer, "{} \"{}\"", node + 1, title.replace('"', "'"))?;
+    }
+
+    writeln!(writer, "*Arcs")?;
+
+    for (source, targets) in network.out_edges.iter().enumerate() {
+        for target in targets {
+            writeln!(writer, "{} {}", source + 1, target + 1)?;
+        }
+    }
+
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::statistics::indexed_network::IndexedNetwork;
+
+    use super::write;
+
+    #[test]
+    fn test_write_pajek() {
+        let network = HashMap::from([
+            ("AT&T".to_string(), vec!["<b>".to_string()]),
+            ("<b>".to_string(), vec![]),
+            ("Say \"Hi\"".to_string(), vec!["AT&T".to_string()])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        let mut output = Vec::new();
+        write(&indexed_network, &mut output).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "*Vertices 3\n1 \"<b>\"\n2 \"AT&T\"\n3 \"Say 'Hi'\"\n*Arcs\n2 1\n3 2\n");
+    }
+}
@@ -0,0 +1,70 @@
+use std::{fs::File, io::{Error, Result}, path::Path, sync::Arc};
+
+use arrow_array::{ArrayRef, BooleanArray, Float64Array, Int64Array, RecordBatch, StringArray, UInt64Array};
+use parquet::arrow::ArrowWriter;
+
+use crate::statistics::indexed_network::IndexedNetwork;
+
+use super::{AttributeValues, NodeAttribute};
+
+const BATCH_SIZE: usize = 1024 * 1024;
+
+/// Writes the edges as two column `source`/`target` table and the nodes as table with an `id`
+/// column matching the edge table, the title and all attributes.
+pub fn write(network: &IndexedNetwork, attributes: &[NodeAttribute], edge_path: &Path, node_path: &Path) -> Result<()> {
+    let mut sources = Vec::with_capacity(BATCH_SIZE);
+    let mut targets = Vec::with_capacity(BATCH_SIZE);
+    let mut edge_writer: Option<ArrowWriter<File>> = None;
+
+    let mut flush_edges = |sources: &mut Vec<u64>, targets: &mut Vec<u64>| -> Result<()> {
+        let batch = RecordBatch::try_from_iter([
+            ("source", Arc::new(UInt64Array::from(std::mem::take(sources))) as ArrayRef),
+            ("target", Arc::new(UInt64Array::from(std::mem::take(targets))) as ArrayRef)
+        ]).map_err(Error::other)?;
+
+        if edge_writer.is_none() {
+            edge_writer = Some(ArrowWriter::try_new(File::create(edge_path)?, batch.schema(), None).map_err(Error::other)?);
+        }
+
+        edge_writer.as_mut().unwrap().write(&batch).map_err(Error::other)
+    };
+
+    for (source, node_targets) in network.out_edges.iter().enumerate() {
+        for target in node_targets {
+            sources.push(source as u64);
+            targets.push(*target as u64);
+
+            if sources.len() == BATCH_SIZE {
+                flush_edges(&mut sources, &mut targets)?;
+            }
+        }
+    }
+
+    flush_edges(&mut sources, &mut targets)?;
+
+    if let Some(edge_writer) = edge_writer {
+        edge_writer.close().map_err(Error::other)?;
+    }
+
+    let mut columns = vec![
+        ("id".to_owned(), Arc::new(UInt64Array::from_iter_values(0..network.len() as u64)) as ArrayRef),
+        ("title".to_owned(), Arc::new(StringArray::from_iter_values(network.nodes.iter().map(|title| title.as_str()))) as ArrayRef)
+    ];
+
+    for attribute in attributes {
+        let column: ArrayRef = match &attribute.values {
+            AttributeValues::Integer(values) => Arc::new(Int64Array::from(values.clone())),
+            AttributeValues::Float(values) => Arc::new(Float64Array::from(values.clone())),
+            AttributeValues::Boolean(values) => Arc::new(BooleanArray::from(values.clone()))
+        };
+
+        columns.push((attribute.name.to_owned(), column));
+    }
+
+    let node_batch = RecordBatch::try_from_iter(columns).map_err(Error::other)?;
+    let mut node_writer = ArrowWriter::try_new(File::create(node_path)?, node_batch.schema(), None).map_err(Error::other)?;
+    node_writer.write(&node_batch).map_err(Error::other)?;
+    node_writer.close().map_err(Error::other)?;
+
+    Ok(())
+}
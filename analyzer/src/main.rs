@@ -7,11 +7,50 @@ mod analyze;
 mod wikitext;
 mod common;
 mod resolve;
+mod communities;
+mod export;
+mod graph_format;
+mod subgraph;
+mod align;
+mod suggest_links;
+mod unlinked_mentions;
+mod plaintext;
+mod sections;
+mod templates;
+mod external_links;
+mod node_attributes;
+mod citations;
+mod categories;
+mod update;
+mod diff;
+mod temporal;
+mod progress;
 
-use crate::network::network;
+use crate::network::{network, DisambiguationHandling, NetworkOptions};
+use crate::update::update_network;
+use crate::diff::diff;
+use crate::temporal::temporal;
+use crate::progress::ProgressFormat;
 use crate::analyze::analyze;
 use crate::wikitext::wikitext;
 use crate::resolve::resolve;
+use crate::communities::communities;
+use crate::export::{export, NODE_ATTRIBUTE_NAMES};
+use crate::graph_format::GraphFormat;
+use crate::subgraph::{subgraph, Direction, Selection, SubgraphOutput};
+use crate::align::{align, LanguageLinkSource};
+use crate::suggest_links::suggest_links;
+use crate::unlinked_mentions::{all_unlinked_mentions, unlinked_mentions};
+use crate::plaintext::{all_plaintext, plaintext};
+use crate::sections::sections;
+use crate::templates::{all_templates, templates};
+use crate::external_links::{article_external_links, domains};
+use crate::citations::{all_citations, citations};
+use crate::categories::category_tree;
+use crate::network_generator::wiki_text::{excluded_sections, EXCLUDED_SECTIONS};
+use crate::network_generator::wiki_xml_dump::Namespaces;
+use crate::common::read_namespaces;
+use crate::statistics::{metric_names, StatisticsOptions};
 
 fn main() -> std::io::Result<()> {
     println!("wikilytics");
@@ -30,43 +69,536 @@ fn main() -> std::io::Result<()> {
             .value_parser(clap::value_parser!(PathBuf)))
             .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
             .value_parser(clap::value_parser!(PathBuf)))
-            .arg(clap::arg!(<TITLE> "Title of the article")
-            .value_parser(clap::value_parser!(String))))
+            .arg(clap::arg!([TITLE] "Title of the article, all articles of the dump if omitted")
+            .value_parser(clap::value_parser!(String))
+            .required_unless_present("unlinked-mentions"))
+            .arg(clap::arg!(--"unlinked-mentions" <NETWORKFILE> "Find titles of articles from the network mentioned outside of links instead")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--redirects <REDIRECTSFILE> "Also find titles of redirects to articles from the network")
+            .value_parser(clap::value_parser!(PathBuf))
+            .requires("unlinked-mentions"))
+            .arg(clap::arg!(--output <OUTPUTFILE> "Where to save the mentions of all articles")
+            .value_parser(clap::value_parser!(PathBuf))
+            .required_unless_present("TITLE")
+            .conflicts_with("TITLE")))
+        .subcommand(clap::command!("plaintext")
+            .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!([TITLE] "Title of the article, all articles of the dump if omitted")
+            .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--output <OUTPUTFILE> "Where to save the text of all articles as JSON lines")
+            .value_parser(clap::value_parser!(PathBuf))
+            .required_unless_present("TITLE")
+            .conflicts_with("TITLE")))
         .subcommand(clap::command!("network")
             .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
             .value_parser(clap::value_parser!(PathBuf)))
             .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
             .value_parser(clap::value_parser!(PathBuf)))
             .arg(clap::arg!(<NETWORKFILE> "Where to save the network")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--namespaces <NAMESPACES> "Comma separated namespaces of the pages in the network, by number or by name like Portal")
+            .value_delimiter(',')
+            .default_value("main"))
+            .arg(clap::arg!(--"exclude-sections" <LANGUAGE> "Leave out links from sections like \"See also\" or \"References\" of this language")
+            .value_parser(clap::builder::PossibleValuesParser::new(EXCLUDED_SECTIONS.map(|(language, _)| language))))
+            .arg(clap::arg!(--"exclude-section" <NAME> "Leave out links from sections with this heading")
+            .action(clap::ArgAction::Append))
+            .arg(clap::arg!(--templates <TEMPLATESFILE> "Where to save the templates of every article as JSON lines")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--template <PREFIX> "Only save templates whose name starts with this prefix, like Infobox")
+            .action(clap::ArgAction::Append)
+            .requires("templates"))
+            .arg(clap::arg!(--nodes <NODESFILE> "Where to save attributes like the number of citations of every article as JSON lines")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--categories <CATEGORYFILE> "Where to save the categories of every article and the parents of every category")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--disambiguation <HANDLING> "Keep disambiguation pages, exclude them or collapse links through them to their targets")
+            .value_parser(clap::builder::PossibleValuesParser::new(DisambiguationHandling::NAMES))
+            .default_value("keep"))
+            .arg(clap::arg!(--resume "Continue an interrupted run from its checkpoint instead of starting over"))
+            .arg(clap::arg!(--progress <FORMAT> "Report the progress as text on the standard output or as JSON events on the standard error")
+            .value_parser(clap::builder::PossibleValuesParser::new(ProgressFormat::NAMES))
+            .default_value("text"))
+            .subcommand_negates_reqs(true)
+            .args_conflicts_with_subcommands(true)
+            .subcommand(clap::command!("update")
+                .about("Patch an existing network with the changed pages of adds-changes dumps")
+                .arg(clap::arg!(<NETWORKFILE> "Path to the network file to update")
+                .value_parser(clap::value_parser!(PathBuf)))
+                .arg(clap::arg!(<CHANGESFILE> ... "Adds-changes xml dumps, oldest first")
+                .value_parser(clap::value_parser!(PathBuf)))
+                .arg(clap::arg!(--redirects <REDIRECTSFILE> "Path to the redirects saved with the network")
+                .value_parser(clap::value_parser!(PathBuf))
+                .default_value("redirects.csv"))
+                .arg(clap::arg!(--deleted <TITLEFILE> "Titles of pages deleted since the network was generated, one per line")
+                .value_parser(clap::value_parser!(PathBuf)))
+                .arg(clap::arg!(--namespaces <NAMESPACES> "Comma separated namespaces of the pages in the network, by number or by name like Portal")
+                .value_delimiter(',')
+                .default_value("main"))))
+        .subcommand(clap::command!("category-tree")
+            .arg(clap::arg!(<CATEGORYFILE> "Path to the category network saved with the network")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<CATEGORY> "Name of the category, with or without the namespace, e.g. Category:Cities")
+            .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--depth <DEPTH> "Number of subcategory levels to include, all if omitted")
+            .value_parser(clap::value_parser!(usize)))
+            .arg(clap::arg!(--output <TITLEFILE> "Where to save the titles of the articles")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--dump <XMLDUMPFILE> "Wikipedia xml dump to read the local name of the category namespace from, only Category works without it")
             .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(clap::command!("citations")
+            .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!([TITLE] "Title of the article, all articles of the dump if omitted")
+            .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--output <OUTPUTFILE> "Where to save the citations of all articles as JSON lines")
+            .value_parser(clap::value_parser!(PathBuf))
+            .required_unless_present("TITLE")
+            .conflicts_with("TITLE")))
+        .subcommand(clap::command!("templates")
+            .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!([TITLE] "Title of the article, all articles of the dump if omitted")
+            .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--template <PREFIX> "Only templates whose name starts with this prefix, like Infobox")
+            .action(clap::ArgAction::Append))
+            .arg(clap::arg!(--output <OUTPUTFILE> "Where to save the templates of all articles as JSON lines")
+            .value_parser(clap::value_parser!(PathBuf))
+            .required_unless_present("TITLE")
+            .conflicts_with("TITLE")))
+        .subcommand(clap::command!("external-links")
+            .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!([TITLE] "Title of the article, all articles of the dump if omitted")
+            .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--output <STATISTICSFILE> "Where to save the number of links to every domain")
+            .value_parser(clap::value_parser!(PathBuf))
+            .required_unless_present("TITLE")
+            .conflicts_with("TITLE"))
+            .arg(clap::arg!(--graph <NETWORKFILE> "Where to save the domains linked by every article as a network")
+            .value_parser(clap::value_parser!(PathBuf))
+            .requires("output")))
+        .subcommand(clap::command!("sections")
+            .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
+            .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<TITLE> "Title of the article")
+            .value_parser(clap::value_parser!(String))))
         .subcommand(clap::command!("analyze")
             .arg(clap::arg!(<NETWORKFILE> "Path to the network file")
                 .value_parser(clap::value_parser!(PathBuf)))
             .arg(clap::arg!(<STATISTICSFILE>)
-                .value_parser(clap::value_parser!(PathBuf))));
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--nodes <NODESFILE> "Node attributes saved with the network, needed by the citations, disambiguation and segments metrics")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--metrics <METRICS> "Comma separated list of the metrics to compute")
+                .value_parser(clap::builder::PossibleValuesParser::new(metric_names()))
+                .value_delimiter(',')
+                .default_value("summary,degrees"))
+            .arg(clap::arg!(--seed <SEED> "Seed for the sampling in betweenness and power law fitting")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("0"))
+            .arg(clap::arg!(--"betweenness-samples" <NUMBER> "Number of source nodes sampled to approximate betweenness")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("100"))
+            .arg(clap::arg!(--"power-law-bootstraps" <NUMBER> "Number of synthetic data sets drawn to estimate the goodness of power law fits")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("100"))
+            .arg(clap::arg!(--top <NUMBER> "Number of articles listed for rankings")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("10"))
+            .arg(clap::arg!(--ccdf "Add the complementary cumulative degree distributions"))
+            .arg(clap::arg!(--"log-bins" <BINS_PER_DECADE> "Add degree distributions in logarithmic bins")
+                .value_parser(clap::value_parser!(u64).range(1..)))
+            .arg(clap::arg!(--"as-of" <TIMESTAMP> "Read the network file as temporal edges and analyze the network at this time, like 2015-01-01 or 2015-01-01T12:00:00Z")
+                .value_parser(clap::value_parser!(String))))
+        .subcommand(clap::command!("communities")
+            .arg(clap::arg!(<NETWORKFILE> "Path to the network file")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<COMMUNITYFILE> "Where to save the community of every article")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<STATISTICSFILE> "Where to save the community statistics")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--seed <SEED> "Seed for the order in which articles are visited")
+                .value_parser(clap::value_parser!(u64))
+                .default_value("0"))
+            .arg(clap::arg!(--resolution <RESOLUTION> "Higher values lead to smaller communities")
+                .value_parser(clap::value_parser!(f64))
+                .default_value("1.0"))
+            .arg(clap::arg!(--top <NUMBER> "Number of articles with the highest PageRank listed per community")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("10")))
+        .subcommand(clap::command!("export")
+            .arg(clap::arg!(<NETWORKFILE> "Path to the network file")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<OUTPUTFILE> "Where to save the converted network")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--format <FORMAT> "Format of the converted network")
+                .value_parser(clap::builder::PossibleValuesParser::new(GraphFormat::NAMES))
+                .default_value("graphml"))
+            .arg(clap::arg!(--attributes <ATTRIBUTES> "Comma separated list of computed node attributes")
+                .value_parser(clap::builder::PossibleValuesParser::new(NODE_ATTRIBUTE_NAMES))
+                .value_delimiter(','))
+            .arg(clap::arg!(--index <XMLDUMPINDEXFILE> "Wikipedia xml dump index file to add page ids")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--nodes <NODESFILE> "Node attributes saved with the network, like the length or the stub flag of every article")
+                .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(clap::command!("subgraph")
+            .arg(clap::arg!(<NETWORKFILE> "Path to the network file")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<OUTPUTFILE> "Where to save the subgraph")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--ego <TITLE> "Extract the neighbourhood of this article, can be repeated")
+                .value_parser(clap::value_parser!(String))
+                .action(clap::ArgAction::Append))
+            .arg(clap::arg!(--hops <HOPS> "Number of links to follow from the ego articles")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("1"))
+            .arg(clap::arg!(--direction <DIRECTION> "Which links to follow from the ego articles")
+                .value_parser(clap::builder::PossibleValuesParser::new(Direction::NAMES))
+                .default_value("both"))
+            .arg(clap::arg!(--titles <TITLEFILE> "Extract the articles listed in this file, one per line")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--category <CATEGORY> "Extract the articles of this category")
+                .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--categories <CATEGORYFILE> "Category network to also extract the articles of subcategories")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("category"))
+            .arg(clap::arg!(--"category-depth" <DEPTH> "Number of subcategory levels to include, all if omitted")
+                .value_parser(clap::value_parser!(usize))
+                .requires("categories"))
+            .group(clap::ArgGroup::new("selection")
+                .args(["ego", "titles", "category"])
+                .required(true))
+            .arg(clap::arg!(--format <FORMAT> "Format of the subgraph")
+                .value_parser(clap::builder::PossibleValuesParser::new(GraphFormat::NAMES))
+                .default_value("network"))
+            .arg(clap::arg!(--attributes <ATTRIBUTES> "Comma separated list of computed node attributes")
+                .value_parser(clap::builder::PossibleValuesParser::new(NODE_ATTRIBUTE_NAMES))
+                .value_delimiter(','))
+            .arg(clap::arg!(--index <XMLDUMPINDEXFILE> "Wikipedia xml dump index file to add page ids")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--dump <XMLDUMPFILE> "Wikipedia xml dump to read the local name of the category namespace from, only Category works without it")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("category")))
+        .subcommand(clap::command!("align")
+            .arg(clap::arg!(<FIRSTNETWORKFILE> "Path to the network file of the first edition")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<SECONDNETWORKFILE> "Path to the network file of the second edition")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<OUTPUTFILE> "Where to save the aligned links")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--language <LANGUAGE> "Language code of the second edition, e.g. de")
+                .value_parser(clap::value_parser!(String))
+                .required(true))
+            .arg(clap::arg!(--dump <XMLDUMPFILE> "Xml dump of the first edition to read interlanguage links from")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("dump-index"))
+            .arg(clap::arg!(--"dump-index" <XMLDUMPINDEXFILE> "Index file of the xml dump of the first edition")
+                .value_parser(clap::value_parser!(PathBuf))
+                .requires("dump"))
+            .arg(clap::arg!(--langlinks <LANGLINKSFILE> "Table of interlanguage links, one 'title;language;target title' per line")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .group(clap::ArgGroup::new("language-links")
+                .args(["dump", "langlinks"])
+                .required(true)))
+        .subcommand(clap::command!("suggest-links")
+            .arg(clap::arg!(<NETWORKFILE> "Path to the network file")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPFILE> "Path to the wikipedia xml dump")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPINDEXFILE> "Path to the wikipedia xml dump index file")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<TITLE> "Title of the article")
+                .value_parser(clap::value_parser!(String)))
+            .arg(clap::arg!(--top <NUMBER> "Number of suggested links")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("20")))
+        .subcommand(clap::command!("temporal")
+            .arg(clap::arg!(<OUTPUTFILE> "Where to save when every link was added and removed, the page intervals are saved next to it")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<XMLDUMPFILE> ... "Paths to the wikipedia xml history dumps")
+                .value_parser(clap::value_parser!(PathBuf))))
+        .subcommand(clap::command!("diff")
+            .arg(clap::arg!(<OLDNETWORKFILE> "Path to the network file of the older snapshot")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<NEWNETWORKFILE> "Path to the network file of the newer snapshot")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(<OUTPUTFILE> "Where to save the changes as JSON")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--redirects <REDIRECTSFILE> "Redirects of the newer snapshot to detect renamed articles")
+                .value_parser(clap::value_parser!(PathBuf)))
+            .arg(clap::arg!(--top <NUMBER> "Number of most changed articles listed")
+                .value_parser(clap::value_parser!(usize))
+                .default_value("10")));
 
     let matches = cmd.get_matches();
     let subcommand = matches.subcommand();
 
     if let Some(("network", matches)) = subcommand {
+        if let Some(("update", matches)) = matches.subcommand() {
+            let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
+            let redirects_file_path = matches.get_one::<PathBuf>("redirects").unwrap();
+            let changes_file_paths = matches.get_many::<PathBuf>("CHANGESFILE").unwrap().cloned().collect();
+            let deleted_file_path = matches.get_one::<PathBuf>("deleted").cloned();
+            let namespaces = matches.get_many::<String>("namespaces").unwrap().cloned().collect();
+
+            update_network(network_file_path.to_owned(), redirects_file_path.to_owned(), changes_file_paths, deleted_file_path, namespaces)?;
+            return Ok(());
+        }
+
         let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
         let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap().to_owned();
         let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
-        network(wiki_xml_dump_path.to_owned(), wiki_xml_dump_index_path, network_file_path.to_owned())?;
+
+        let language_sections = matches.get_one::<String>("exclude-sections")
+            .and_then(|language| excluded_sections(language))
+            .unwrap_or_default()
+            .iter()
+            .map(|name| name.to_string());
+        let named_sections = matches.get_many::<String>("exclude-section")
+            .unwrap_or_default()
+            .cloned();
+
+        let options = NetworkOptions {
+            namespaces: matches.get_many::<String>("namespaces").unwrap().cloned().collect(),
+            excluded_sections: language_sections.chain(named_sections)
+                .map(|name| name.to_lowercase())
+                .collect(),
+            templates_file_path: matches.get_one::<PathBuf>("templates").cloned(),
+            template_prefixes: template_prefixes(matches),
+            nodes_file_path: matches.get_one::<PathBuf>("nodes").cloned(),
+            categories_file_path: matches.get_one::<PathBuf>("categories").cloned(),
+            disambiguation: DisambiguationHandling::from_name(matches.get_one::<String>("disambiguation").unwrap()).unwrap(),
+            resume: matches.get_flag("resume"),
+            progress: ProgressFormat::from_name(matches.get_one::<String>("progress").unwrap()).unwrap()
+        };
+
+        network(wiki_xml_dump_path.to_owned(), wiki_xml_dump_index_path, network_file_path.to_owned(), options)?;
+    }
+
+    if let Some(("templates", matches)) = subcommand {
+        let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
+        let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
+        let prefixes = template_prefixes(matches);
+
+        if let Some(article_title) = matches.get_one::<String>("TITLE") {
+            templates(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title, &prefixes)?;
+        } else {
+            let output_file_path = matches.get_one::<PathBuf>("output").unwrap();
+            all_templates(wiki_xml_dump_path, wiki_xml_dump_index_path, output_file_path.to_owned(), &prefixes)?;
+        }
+    }
+
+    if let Some(("citations", matches)) = subcommand {
+        let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
+        let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
+
+        if let Some(article_title) = matches.get_one::<String>("TITLE") {
+            citations(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title)?;
+        } else {
+            let output_file_path = matches.get_one::<PathBuf>("output").unwrap();
+            all_citations(wiki_xml_dump_path, wiki_xml_dump_index_path, output_file_path.to_owned())?;
+        }
+    }
+
+    if let Some(("external-links", matches)) = subcommand {
+        let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
+        let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
+
+        if let Some(article_title) = matches.get_one::<String>("TITLE") {
+            article_external_links(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title)?;
+        } else {
+            let statistics_file_path = matches.get_one::<PathBuf>("output").unwrap();
+            let graph_file_path = matches.get_one::<PathBuf>("graph").cloned();
+            domains(wiki_xml_dump_path, wiki_xml_dump_index_path, statistics_file_path.to_owned(), graph_file_path)?;
+        }
+    }
+
+    if let Some(("category-tree", matches)) = subcommand {
+        let category_file_path = matches.get_one::<PathBuf>("CATEGORYFILE").unwrap();
+        let category = matches.get_one::<String>("CATEGORY").unwrap();
+        let depth = matches.get_one::<usize>("depth").copied();
+        let output_file_path = matches.get_one::<PathBuf>("output").cloned();
+
+        category_tree(category_file_path.to_owned(), category, &category_namespace(matches)?, depth, output_file_path)?;
+    }
+
+    if let Some(("sections", matches)) = subcommand {
+        let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
+        let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
+        let article_title = matches.get_one::<String>("TITLE").unwrap();
+
+        sections(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title)?;
     }
 
     if let Some(("analyze", matches)) = subcommand {
         let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
         let statistics_file_path = matches.get_one::<PathBuf>("STATISTICSFILE").unwrap();
-        analyze(network_file_path.to_owned(), statistics_file_path.to_owned())?;
+        let metrics = matches.get_many::<String>("metrics").unwrap()
+            .cloned()
+            .collect();
+        let options = StatisticsOptions {
+            seed: *matches.get_one::<u64>("seed").unwrap(),
+            betweenness_samples: *matches.get_one::<usize>("betweenness-samples").unwrap(),
+            power_law_bootstraps: *matches.get_one::<usize>("power-law-bootstraps").unwrap(),
+            number_of_top_nodes: *matches.get_one::<usize>("top").unwrap(),
+            ccdf: matches.get_flag("ccdf"),
+            log_bins_per_decade: matches.get_one::<u64>("log-bins").map(|bins| *bins as usize)
+        };
+
+        let node_file_path = matches.get_one::<PathBuf>("nodes").cloned();
+
+        let as_of = matches.get_one::<String>("as-of").cloned();
+
+        analyze(network_file_path.to_owned(), statistics_file_path.to_owned(), node_file_path, metrics, options, as_of)?;
     }
 
-    if let Some(("wikitext", matches)) = subcommand {
+    if let Some(("communities", matches)) = subcommand {
+        let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
+        let community_file_path = matches.get_one::<PathBuf>("COMMUNITYFILE").unwrap();
+        let statistics_file_path = matches.get_one::<PathBuf>("STATISTICSFILE").unwrap();
+        let seed = *matches.get_one::<u64>("seed").unwrap();
+        let resolution = *matches.get_one::<f64>("resolution").unwrap();
+        let number_of_top_members = *matches.get_one::<usize>("top").unwrap();
+
+        communities(network_file_path.to_owned(), community_file_path.to_owned(), statistics_file_path.to_owned(), seed, resolution, number_of_top_members)?;
+    }
+
+    if let Some(("export", matches)) = subcommand {
+        let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
+        let output_file_path = matches.get_one::<PathBuf>("OUTPUTFILE").unwrap();
+        let format = GraphFormat::from_name(matches.get_one::<String>("format").unwrap()).unwrap();
+        let attribute_names = matches.get_many::<String>("attributes")
+            .map(|names| names.cloned().collect())
+            .unwrap_or_default();
+        let xml_dump_index_path = matches.get_one::<PathBuf>("index").cloned();
+        let node_file_path = matches.get_one::<PathBuf>("nodes").cloned();
+
+        export(network_file_path.to_owned(), output_file_path.to_owned(), format, attribute_names, xml_dump_index_path, node_file_path)?;
+    }
+
+    if let Some(("subgraph", matches)) = subcommand {
+        let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
+        let output_file_path = matches.get_one::<PathBuf>("OUTPUTFILE").unwrap();
+
+        let selection = if let Some(titles) = matches.get_many::<String>("ego") {
+            Selection::Ego {
+                titles: titles.cloned().collect(),
+                hops: *matches.get_one::<usize>("hops").unwrap(),
+                direction: Direction::from_name(matches.get_one::<String>("direction").unwrap()).unwrap()
+            }
+        } else if let Some(title_file_path) = matches.get_one::<PathBuf>("titles") {
+            Selection::TitleFile(title_file_path.to_owned())
+        } else if let Some(category_file_path) = matches.get_one::<PathBuf>("categories") {
+            Selection::CategoryTree {
+                category: matches.get_one::<String>("category").unwrap().to_owned(),
+                category_namespace: category_namespace(matches)?,
+                category_file_path: category_file_path.to_owned(),
+                depth: matches.get_one::<usize>("category-depth").copied()
+            }
+        } else {
+            Selection::Category {
+                category: matches.get_one::<String>("category").unwrap().to_owned(),
+                category_namespace: category_namespace(matches)?
+            }
+        };
+
+        let output = SubgraphOutput {
+            format: GraphFormat::from_name(matches.get_one::<String>("format").unwrap()).unwrap(),
+            attribute_names: matches.get_many::<String>("attributes")
+                .map(|names| names.cloned().collect())
+                .unwrap_or_default(),
+            xml_dump_index_path: matches.get_one::<PathBuf>("index").cloned()
+        };
+
+        subgraph(network_file_path.to_owned(), output_file_path.to_owned(), selection, output)?;
+    }
+
+    if let Some(("align", matches)) = subcommand {
+        let first_network_file_path = matches.get_one::<PathBuf>("FIRSTNETWORKFILE").unwrap();
+        let second_network_file_path = matches.get_one::<PathBuf>("SECONDNETWORKFILE").unwrap();
+        let output_file_path = matches.get_one::<PathBuf>("OUTPUTFILE").unwrap();
+        let language = matches.get_one::<String>("language").unwrap();
+
+        let language_link_source = match matches.get_one::<PathBuf>("dump") {
+            Some(xml_dump_path) => LanguageLinkSource::Dump {
+                xml_dump_path: xml_dump_path.to_owned(),
+                xml_dump_index_path: matches.get_one::<PathBuf>("dump-index").unwrap().to_owned()
+            },
+            None => LanguageLinkSource::Table(matches.get_one::<PathBuf>("langlinks").unwrap().to_owned())
+        };
+
+        align(first_network_file_path.to_owned(), second_network_file_path.to_owned(), output_file_path.to_owned(), language.to_owned(), language_link_source)?;
+    }
+
+    if let Some(("suggest-links", matches)) = subcommand {
+        let network_file_path = matches.get_one::<PathBuf>("NETWORKFILE").unwrap();
         let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
         let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
         let article_title = matches.get_one::<String>("TITLE").unwrap();
+        let number_of_suggestions = *matches.get_one::<usize>("top").unwrap();
 
-        wikitext(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title)?;
+        suggest_links(network_file_path.to_owned(), wiki_xml_dump_path, wiki_xml_dump_index_path, article_title, number_of_suggestions)?;
+    }
+
+    if let Some(("temporal", matches)) = subcommand {
+        let output_file_path = matches.get_one::<PathBuf>("OUTPUTFILE").unwrap();
+        let xml_dump_paths = matches.get_many::<PathBuf>("XMLDUMPFILE").unwrap().cloned().collect();
+
+        temporal(xml_dump_paths, output_file_path.to_owned())?;
+    }
+
+    if let Some(("diff", matches)) = subcommand {
+        let old_network_file_path = matches.get_one::<PathBuf>("OLDNETWORKFILE").unwrap();
+        let new_network_file_path = matches.get_one::<PathBuf>("NEWNETWORKFILE").unwrap();
+        let output_file_path = matches.get_one::<PathBuf>("OUTPUTFILE").unwrap();
+        let redirects_file_path = matches.get_one::<PathBuf>("redirects").cloned();
+        let number_of_top_nodes = *matches.get_one::<usize>("top").unwrap();
+
+        diff(old_network_file_path.to_owned(), new_network_file_path.to_owned(), output_file_path.to_owned(), redirects_file_path, number_of_top_nodes)?;
+    }
+
+    if let Some(("wikitext", matches)) = subcommand {
+        let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
+        let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
+        let article_title = matches.get_one::<String>("TITLE");
+        let unlinked_mentions_network_path = matches.get_one::<PathBuf>("unlinked-mentions");
+        let redirects_file_path = matches.get_one::<PathBuf>("redirects").cloned();
+
+        match (article_title, unlinked_mentions_network_path) {
+            (Some(article_title), None) => wikitext(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title)?,
+            (Some(article_title), Some(network_file_path)) => unlinked_mentions(wiki_xml_dump_path, wiki_xml_dump_index_path,
+                article_title, network_file_path.to_owned(), redirects_file_path)?,
+            (None, Some(network_file_path)) => {
+                let output_file_path = matches.get_one::<PathBuf>("output").unwrap();
+                all_unlinked_mentions(wiki_xml_dump_path, wiki_xml_dump_index_path, output_file_path.to_owned(),
+                    network_file_path.to_owned(), redirects_file_path)?
+            },
+            (None, None) => unreachable!()
+        }
+    }
+
+    if let Some(("plaintext", matches)) = subcommand {
+        let wiki_xml_dump_path = matches.get_one::<PathBuf>("XMLDUMPFILE").unwrap();
+        let wiki_xml_dump_index_path = matches.get_one::<PathBuf>("XMLDUMPINDEXFILE").unwrap();
+
+        if let Some(article_title) = matches.get_one::<String>("TITLE") {
+            plaintext(wiki_xml_dump_path, wiki_xml_dump_index_path, article_title)?;
+        } else {
+            let output_file_path = matches.get_one::<PathBuf>("output").unwrap();
+            all_plaintext(wiki_xml_dump_path, wiki_xml_dump_index_path, output_file_path.to_owned())?;
+        }
     }
 
     if let Some(("resolve", matches)) = subcommand {
@@ -79,3 +611,19 @@ fn main() -> std::io::Result<()> {
 
     Ok(())
 }
+
+/// The local name of the category namespace from the siteinfo of the `--dump`, the English name
+/// without a dump.
+fn category_namespace(matches: &clap::ArgMatches) -> std::io::Result<String> {
+    match matches.get_one::<PathBuf>("dump") {
+        Some(xml_dump_path) => Ok(read_namespaces(xml_dump_path)?.name(Namespaces::CATEGORY).unwrap_or("Category").to_owned()),
+        None => Ok("Category".to_owned())
+    }
+}
+
+fn template_prefixes(matches: &clap::ArgMatches) -> Vec<String> {
+    matches.get_many::<String>("template")
+        .unwrap_or_default()
+        .map(|prefix| prefix.to_lowercase())
+        .collect()
+}
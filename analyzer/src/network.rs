@@ -1,153 +1,499 @@
-use std::{collections::{HashMap, HashSet}, fs::{remove_file, rename, File}, io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Read, Seek, SeekFrom, Write}, path::PathBuf, sync::mpsc::{channel, Sender}, thread};
+use std::{collections::{BTreeSet, HashMap, HashSet}, fs::{create_dir_all, remove_dir_all, remove_file, rename, File}, io::{self, BufRead, BufReader, BufWriter, Error, ErrorKind, Write}, path::{Path, PathBuf}, sync::{atomic::{AtomicUsize, Ordering}, mpsc::{channel, RecvTimeoutError}}, thread};
 use std::io::Result;
 
-use bzip2::bufread::MultiBzDecoder;
 use itertools::Itertools;
-use quick_xml::Reader;
+use serde::{Deserialize, Serialize};
+
+use crate::{analyze::load_network, common::{read_from_to_with_progress, read_namespaces, split_dump}, progress::{Progress, ProgressFormat, REPORT_INTERVAL}, node_attributes::{node_record, write_node_record}, templates::write_templates, network_generator::{canonicalize_link, wiki_text::{category_links, category_title, is_disambiguation, is_redirect, linked_articles, parse_templates, parse_text, parse_text_with_sections, redirects_to}, wiki_xml_dump::{Namespaces, WikiPage}}};
+
+/// What happens to disambiguation pages and the links to them.
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum DisambiguationHandling {
+    /// Disambiguation pages are nodes like any other article.
+    #[default]
+    Keep,
+    /// Disambiguation pages and the links to them are left out.
+    Exclude,
+    /// Links to a disambiguation page are replaced by the links of the disambiguation page.
+    Collapse
+}
+
+impl DisambiguationHandling {
+    pub const NAMES: [&'static str; 3] = ["keep", "exclude", "collapse"];
 
-use crate::{common::read_from_to, network_generator::{canonicalize_link, generate_network, generate_network_parrallel, wiki_text::{is_redirect, linked_articles, parse_text, redirects_to}, wiki_xml_dump::{blocks, read_index, WikiPage, WikiXmlDump}}};
+    pub fn from_name(name: &str) -> Option<DisambiguationHandling> {
+        match name {
+            "keep" => Some(DisambiguationHandling::Keep),
+            "exclude" => Some(DisambiguationHandling::Exclude),
+            "collapse" => Some(DisambiguationHandling::Collapse),
+            _ => None
+        }
+    }
+}
 
-pub fn network(xml_dump_path: PathBuf, dump_index_path: PathBuf, network_file_path: PathBuf) -> Result<()> {
+/// Number of parts the dump is split into. Every finished part is recorded in the checkpoint
+/// manifest, so an interrupted run can continue with the remaining ones.
+const NUMBER_OF_PARTS: usize = 64;
+
+#[derive(Default, Clone)]
+pub struct NetworkOptions {
+    /// Namespaces of the pages in the network by number or name, only articles if empty.
+    pub namespaces: Vec<String>,
+    /// Lowercase names of sections whose links are left out, together with their subsections.
+    pub excluded_sections: Vec<String>,
+    /// Where to save the templates of every article as JSON lines.
+    pub templates_file_path: Option<PathBuf>,
+    /// Lowercase prefixes of the names of the saved templates, all templates if empty.
+    pub template_prefixes: Vec<String>,
+    /// Where to save the attributes of every article as JSON lines.
+    pub nodes_file_path: Option<PathBuf>,
+    /// Where to save the categories of every article and the parents of every category in the
+    /// network format.
+    pub categories_file_path: Option<PathBuf>,
+    pub disambiguation: DisambiguationHandling,
+    /// Continue with the parts an interrupted run did not finish.
+    pub resume: bool,
+    pub progress: ProgressFormat
+}
+
+pub fn network(xml_dump_path: PathBuf, dump_index_path: PathBuf, network_file_path: PathBuf, options: NetworkOptions) -> Result<()> {
     let number_of_threads = 4;
-    
-    let start_positions = read_index_file(dump_index_path)?;
-    let block_size = start_positions.len() / number_of_threads + 1;
-    let mut blocks = Vec::with_capacity(number_of_threads + 1);
-    blocks.push(0);
-
-    for thread_number in 1..number_of_threads {
-        let start_index = thread_number * block_size;
-        blocks.push(start_positions[start_index]);
+
+    let namespaces = read_namespaces(&xml_dump_path)?;
+    let selected_namespaces = select_namespaces(&namespaces, &options.namespaces)?;
+    let category_namespace = namespaces.name(Namespaces::CATEGORY).unwrap_or("Category").to_owned();
+
+    let parts = split_dump(&xml_dump_path, &dump_index_path, NUMBER_OF_PARTS)?;
+    let checkpoint_directory = network_file_path.with_extension("parts");
+    let mut manifest = start_checkpoint(&checkpoint_directory, parts.clone(), checkpoint_settings(&options, &selected_namespaces), options.resume)?;
+
+    let pending_parts = (0..parts.len())
+        .filter(|part| !manifest.completed.contains(part))
+        .collect::<Vec<usize>>();
+    let next_part = AtomicUsize::new(0);
+
+    println!("{} of {} parts left", pending_parts.len(), parts.len());
+
+    let file_size = File::open(&xml_dump_path)?.metadata()?.len();
+    let part_size = |part: &usize| parts[*part].1.min(file_size) - parts[*part].0;
+    let progress = Progress::new(options.progress, file_size, manifest.completed.iter().map(part_size).sum());
+
+    let (finish_sender, finish_receiver) = channel();
+
+    thread::scope(|scope| {
+        for thread_number in 0..number_of_threads {
+            let finish_sender = finish_sender.clone();
+            let (xml_dump_path, checkpoint_directory, parts, pending_parts, next_part) = (&xml_dump_path, &checkpoint_directory, &parts, &pending_parts, &next_part);
+            let (options, selected_namespaces, category_namespace, progress) = (&options, &selected_namespaces, &category_namespace, &progress);
+
+            scope.spawn(move || {
+                println!("starting decoding thread {thread_number}");
+
+                while let Some(part) = pending_parts.get(next_part.fetch_add(1, Ordering::SeqCst)) {
+                    let result = PartWriters::create(&PartFiles::new(checkpoint_directory, *part), options)
+                        .and_then(|mut writers| {
+                            process_partial_dump(xml_dump_path, parts[*part], &mut writers, options, selected_namespaces, category_namespace, progress)?;
+                            writers.flush()
+                        })
+                        .map(|_| *part);
+                    let failed = result.is_err();
+
+                    if finish_sender.send(result).is_err() || failed {
+                        break;
+                    }
+                }
+
+                println!("finished decoding thread {thread_number}");
+            });
+        }
+
+        drop(finish_sender);
+
+        loop {
+            match finish_receiver.recv_timeout(REPORT_INTERVAL) {
+                Ok(Ok(part)) => {
+                    manifest.completed.insert(part);
+                    save_manifest(&checkpoint_directory, &manifest)?;
+                    progress.part_finished(part, manifest.completed.len(), parts.len());
+                },
+                Ok(Err(error)) => {
+                    // the other threads finish their current part, which is kept for --resume
+                    next_part.store(pending_parts.len(), Ordering::SeqCst);
+
+                    for part in finish_receiver.iter().flatten() {
+                        manifest.completed.insert(part);
+                        save_manifest(&checkpoint_directory, &manifest)?;
+                    }
+
+                    return Err(error);
+                },
+                Err(RecvTimeoutError::Timeout) => progress.report(),
+                Err(RecvTimeoutError::Disconnected) => break
+            }
+        }
+
+        progress.report();
+        progress.finish();
+
+        Ok::<(), Error>(())
+    })?;
+
+    println!("merging the parts");
+    let part_files = (0..parts.len())
+        .map(|part| PartFiles::new(&checkpoint_directory, part))
+        .collect::<Vec<PartFiles>>();
+
+    merge_parts(part_files.iter().map(|files| &files.adjacency), &network_file_path)?;
+    merge_parts(part_files.iter().map(|files| &files.redirects), &PathBuf::from("redirects.csv"))?;
+
+    if let Some(templates_file_path) = &options.templates_file_path {
+        merge_parts(part_files.iter().map(|files| &files.templates), templates_file_path)?;
     }
 
-    let file_size = File::open(xml_dump_path.clone())?.metadata()?.len();
-    blocks.push(file_size + 1);
+    if let Some(nodes_file_path) = &options.nodes_file_path {
+        merge_parts(part_files.iter().map(|files| &files.nodes), nodes_file_path)?;
+    }
 
-    println!("{:?}", blocks);
+    if let Some(categories_file_path) = &options.categories_file_path {
+        merge_parts(part_files.iter().map(|files| &files.categories), categories_file_path)?;
+    }
 
-    let (adjacency_sender, adjacency_receiver) = channel();
-    let (redirect_sender, redirect_receiver) = channel();
+    println!("starting to process redirects");
+    close_redirects(PathBuf::from("redirects.csv"))?;
+    println!("finished closing redirects");
 
-    for thread_number in 0..number_of_threads {
-        let block_start = blocks[thread_number];
-        let block_end = blocks[thread_number + 1];
-        let xml_dump_path = xml_dump_path.clone();
-        let adjacency_sender = adjacency_sender.clone();
-        let redirect_sender = redirect_sender.clone();
+    if options.disambiguation != DisambiguationHandling::Keep {
+        let mut disambiguation_pages = HashMap::new();
 
-        thread::spawn(move || {
-            println!("starting decoding thread {thread_number}");
-            process_partial_dump(xml_dump_path, block_start, block_end, adjacency_sender, redirect_sender).unwrap();
-            println!("finished decoding thread {thread_number}");
-        });
+        for files in &part_files {
+            disambiguation_pages.extend(load_network(files.disambiguations.to_owned())?);
+        }
+
+        println!("removing links to {} disambiguation pages", disambiguation_pages.len());
+        remove_disambiguation_links(network_file_path, &disambiguation_pages, options.disambiguation)?;
     }
 
-    drop(redirect_sender);
-    drop(adjacency_sender);
+    remove_dir_all(checkpoint_directory)
+}
 
-    let (finish_sender, finish_receiver) = channel();
+/// Which parts of the dump are finished, saved in the checkpoint directory after every part.
+#[derive(Serialize, Deserialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+struct CheckpointManifest {
+    /// The options which change the partial outputs, a run can only be resumed with the same ones.
+    settings: String,
+    parts: Vec<(u64, u64)>,
+    completed: BTreeSet<usize>
+}
 
-    {
-        let finish_sender = finish_sender.clone();
-        thread::spawn(move || {
-            println!("starting to save network");
-            save_network(adjacency_receiver.into_iter(), network_file_path).unwrap();
-            println!("finished saving the network");
-            finish_sender.send(()).unwrap();
-        });
+fn checkpoint_settings(options: &NetworkOptions, selected_namespaces: &[i16]) -> String {
+    format!("namespaces {:?}, excluded sections {:?}, templates {:?}, template prefixes {:?}, nodes {:?}, categories {:?}, disambiguation {:?}",
+        selected_namespaces,
+        options.excluded_sections,
+        options.templates_file_path.is_some(),
+        options.template_prefixes,
+        options.nodes_file_path.is_some(),
+        options.categories_file_path.is_some(),
+        options.disambiguation)
+}
+
+/// Loads the manifest of an interrupted run when resuming, otherwise starts with an empty
+/// checkpoint directory.
+fn start_checkpoint(checkpoint_directory: &Path, parts: Vec<(u64, u64)>, settings: String, resume: bool) -> Result<CheckpointManifest> {
+    let manifest_path = checkpoint_directory.join("manifest.json");
+
+    if resume && manifest_path.exists() {
+        let manifest = serde_json::from_reader::<_, CheckpointManifest>(BufReader::new(File::open(manifest_path)?))?;
+
+        if manifest.parts != parts {
+            return Err(Error::new(ErrorKind::InvalidInput, "the checkpoint belongs to a different dump"));
+        }
+
+        if manifest.settings != settings {
+            return Err(Error::new(ErrorKind::InvalidInput, format!("the checkpoint was made with other options: {}", manifest.settings)));
+        }
+
+        println!("resuming with {} finished parts", manifest.completed.len());
+        return Ok(manifest);
     }
 
-    thread::spawn(move || {
-        println!("starting to process redirects");
-        save_redirects(redirect_receiver.into_iter(), PathBuf::from("redirects.csv")).unwrap();
-        println!("finished saving redirects");
-        close_redirects(PathBuf::from("redirects.csv")).unwrap();
-        println!("finished closing redirects");
+    if resume {
+        println!("no checkpoint found, starting from the beginning");
+    }
 
-        finish_sender.send(()).unwrap();
-    });
+    if checkpoint_directory.exists() {
+        remove_dir_all(checkpoint_directory)?;
+    }
 
-    finish_receiver.recv().unwrap();
-    finish_receiver.recv().unwrap();
+    create_dir_all(checkpoint_directory)?;
 
-    Ok(())
+    let manifest = CheckpointManifest { settings, parts, completed: BTreeSet::new() };
+    save_manifest(checkpoint_directory, &manifest)?;
+
+    Ok(manifest)
+}
+
+/// Replaces the manifest at once, so a crash never leaves a half written one behind.
+fn save_manifest(checkpoint_directory: &Path, manifest: &CheckpointManifest) -> Result<()> {
+    let temporary_manifest_path = checkpoint_directory.join("manifest.temp");
+    let mut writer = BufWriter::new(File::create(&temporary_manifest_path)?);
+
+    serde_json::to_writer(&mut writer, manifest)?;
+    writer.flush()?;
+    drop(writer);
+
+    rename(temporary_manifest_path, checkpoint_directory.join("manifest.json"))
 }
 
-fn process_partial_dump(xml_dump_path: PathBuf, block_start: u64, block_end: u64, adjacency_sender: Sender<(String, Vec<String>)>, redirect_sender: Sender<(String, String)>) -> Result<()> {
-    let xml_dump = read_from_to(&xml_dump_path, block_start, block_end)?;
+/// Where the outputs of one part of the dump are saved until all parts are merged.
+struct PartFiles {
+    adjacency: PathBuf,
+    redirects: PathBuf,
+    templates: PathBuf,
+    nodes: PathBuf,
+    categories: PathBuf,
+    disambiguations: PathBuf
+}
+
+impl PartFiles {
+    fn new(checkpoint_directory: &Path, part: usize) -> PartFiles {
+        let path = |extension: &str| checkpoint_directory.join(format!("{part}.{extension}"));
+
+        PartFiles {
+            adjacency: path("network"),
+            redirects: path("redirects"),
+            templates: path("templates"),
+            nodes: path("nodes"),
+            categories: path("categories"),
+            disambiguations: path("disambiguations")
+        }
+    }
+}
+
+/// The outputs of one part of the dump, the optional ones only exist if they were asked for.
+struct PartWriters {
+    adjacency: BufWriter<File>,
+    redirects: BufWriter<File>,
+    templates: Option<BufWriter<File>>,
+    nodes: Option<BufWriter<File>>,
+    categories: Option<BufWriter<File>>,
+    disambiguations: Option<BufWriter<File>>
+}
+
+impl PartWriters {
+    fn create(files: &PartFiles, options: &NetworkOptions) -> Result<PartWriters> {
+        let writer = |path: &PathBuf| File::create(path).map(BufWriter::new);
+        let optional_writer = |path: &PathBuf, enabled: bool| enabled.then(|| writer(path)).transpose();
+
+        Ok(PartWriters {
+            adjacency: writer(&files.adjacency)?,
+            redirects: writer(&files.redirects)?,
+            templates: optional_writer(&files.templates, options.templates_file_path.is_some())?,
+            nodes: optional_writer(&files.nodes, options.nodes_file_path.is_some())?,
+            categories: optional_writer(&files.categories, options.categories_file_path.is_some())?,
+            disambiguations: optional_writer(&files.disambiguations, options.disambiguation != DisambiguationHandling::Keep)?
+        })
+    }
+
+    fn flush(&mut self) -> Result<()> {
+        self.adjacency.flush()?;
+        self.redirects.flush()?;
+
+        for writer in [&mut self.templates, &mut self.nodes, &mut self.categories, &mut self.disambiguations].into_iter().flatten() {
+            writer.flush()?;
+        }
+
+        Ok(())
+    }
+}
+
+/// The ids of the namespaces given by number or name, only the main namespace if none are given.
+pub fn select_namespaces(namespaces: &Namespaces, selection: &[String]) -> Result<Vec<i16>> {
+    if selection.is_empty() {
+        return Ok(vec![Namespaces::MAIN]);
+    }
+
+    selection.iter()
+        .map(|namespace| namespaces.id(namespace)
+            .ok_or(Error::new(ErrorKind::InvalidInput, format!("unknown namespace {namespace}"))))
+        .collect()
+}
+
+fn process_partial_dump(xml_dump_path: &PathBuf, (block_start, block_end): (u64, u64), writers: &mut PartWriters, options: &NetworkOptions, selected_namespaces: &[i16], category_namespace: &str, progress: &Progress) -> Result<()> {
+    let xml_dump = read_from_to_with_progress(xml_dump_path, block_start, block_end, progress)?;
 
     for page in xml_dump {
-        if page.namespace_id == 0 {
-            let links = parse_text(&page);
-    
-            if let Some(links) = links {
-                
-                if !is_redirect(&links) {
-                    let links = linked_articles(&links).iter()
+        let number_of_links = process_page(page, writers, options, selected_namespaces, category_namespace)?;
+        progress.add_page(number_of_links);
+    }
+
+    Ok(())
+}
+
+/// Writes the outputs of a page and returns the number of links added to the network.
+fn process_page(page: WikiPage, writers: &mut PartWriters, options: &NetworkOptions, selected_namespaces: &[i16], category_namespace: &str) -> Result<usize> {
+    let mut number_of_links = 0;
+
+    if selected_namespaces.contains(&page.namespace_id) {
+        let parsed = if options.excluded_sections.is_empty() {
+            parse_text(&page).map(|links| (links, None))
+        } else {
+            parse_text_with_sections(&page).map(|(links, sections)| (links, Some(sections)))
+        };
+
+        if let Some((links, sections)) = parsed {
+            
+            if !is_redirect(&links) {
+                if let Some(writer) = &mut writers.categories {
+                    write_adjacency(writer, &category_file_title(&page), &category_links(&links, category_namespace))?;
+                }
+
+                let links: Vec<String> = match sections {
+                    None => linked_articles(&links).iter()
                         .filter_map(|link| canonicalize_link(*link))
-                        .collect();
-                    adjacency_sender.send((page.title, links)).unwrap();
+                        .collect(),
+                    Some(sections) => sections.iter()
+                        .filter(|section| !section.is_within(&options.excluded_sections))
+                        .flat_map(|section| section.links.iter())
+                        .filter_map(canonicalize_link)
+                        .collect()
+                };
+
+                let text = page.text.as_deref().unwrap_or_default();
+                let templates = if writers.templates.is_some() || writers.nodes.is_some() || writers.disambiguations.is_some() {
+                    parse_templates(text)
                 } else {
-                    if let Some(target) = redirects_to(&links).and_then(canonicalize_link) {
+                    Vec::new()
+                };
 
-                        redirect_sender.send((page.title, target)).unwrap();
-                    } 
+                if let Some(writer) = &mut writers.templates {
+                    let prefixes = &options.template_prefixes;
+                    write_templates(writer, &page.title, templates.iter().filter(|template| prefixes.is_empty() || template.has_prefix(prefixes)))?;
+                }
+
+                if let Some(writer) = &mut writers.nodes {
+                    write_node_record(writer, &node_record(&page, &links, &templates))?;
+                }
+
+                number_of_links = links.len();
+
+                match &mut writers.disambiguations {
+                    Some(writer) if is_disambiguation(text, &templates) => write_adjacency(writer, &page.title, &links)?,
+                    _ => write_adjacency(&mut writers.adjacency, &page.title, &links)?
                 }
+            } else {
+                if let Some(target) = redirects_to(&links).and_then(canonicalize_link) {
+
+                    write_redirect(&mut writers.redirects, &page.title, &target)?;
+                } 
+            }
+        }
+    } else if page.namespace_id == Namespaces::CATEGORY {
+        if let Some(writer) = &mut writers.categories {
+            if let Some(links) = parse_text(&page).filter(|links| !is_redirect(links)) {
+                write_adjacency(writer, &category_file_title(&page), &category_links(&links, category_namespace))?;
             }
         }
     }
 
-    Ok(())
+    Ok(number_of_links)
+}
+
+/// The title of a page in the category network, where category pages have the `Category:` prefix
+/// like the categories they are in.
+fn category_file_title(page: &WikiPage) -> String {
+    match page.title.split_once(':') {
+        Some((_, name)) if page.namespace_id == Namespaces::CATEGORY => category_title(name).unwrap_or(page.title.to_owned()),
+        _ => page.title.to_owned()
+    }
+}
+
+/// Concatenates the outputs of all parts in the order of the dump.
+fn merge_parts<'a>(part_file_paths: impl Iterator<Item = &'a PathBuf>, save_file_path: &PathBuf) -> Result<()> {
+    let mut writer = BufWriter::with_capacity(16 * 1024 * 1024, File::create(save_file_path)?);
+
+    for part_file_path in part_file_paths {
+        io::copy(&mut File::open(part_file_path)?, &mut writer)?;
+    }
+
+    writer.flush()
 }
 
-fn read_index_file(dump_index_path: PathBuf) -> Result<Vec<u64>> {
-    let file = File::open(dump_index_path)?;
-    let file_reader = BufReader::new(file);
-    let bz_decoder = MultiBzDecoder::new(file_reader);
+fn write_adjacency<W: Write>(writer: &mut W, node: &str, connected_nodes: &[String]) -> Result<()> {
+    writer.write_all(node.as_bytes())?;
 
-    let bz_reader = BufReader::new(bz_decoder);
+    for connected_node in connected_nodes {
+        writer.write_all(b"; ")?;
+        writer.write_all(connected_node.as_bytes())?;
+    }
 
-    let index = read_index(bz_reader)?;
+    writer.write_all(b"\n")
+}
 
-    Ok(blocks(index))
+fn write_redirect<W: Write>(writer: &mut W, node: &str, target: &str) -> Result<()> {
+    writer.write_all(node.as_bytes())?;
+    writer.write_all(b";")?;
+    writer.write_all(target.as_bytes())?;
+    writer.write_all(b"\n")
 }
 
-fn save_network<T>(network_to_save: T, save_file_path: PathBuf) -> Result<()> where T: Iterator<Item = (String, Vec<String>)> {
-    let save_file = File::create(save_file_path)?;
-    let mut file_writer = BufWriter::with_capacity(128 * 1024 * 1024, save_file);
+/// Rewrites the saved network without the links to disambiguation pages, which either get dropped
+/// or replaced by the links of the disambiguation page.
+fn remove_disambiguation_links(network_file_path: PathBuf, disambiguation_pages: &HashMap<String, Vec<String>>, handling: DisambiguationHandling) -> Result<()> {
+    let temporary_network_file_path = network_file_path.with_extension("temp");
+    let reader = BufReader::new(File::open(&network_file_path)?);
+    let mut writer = BufWriter::new(File::create(&temporary_network_file_path)?);
 
-    let separator = "; ".as_bytes();
-    let newline = "\n".as_bytes();
+    for line in reader.lines() {
+        let line = line?;
+        let mut nodes = line.split(';').map(|node| node.trim_start());
+
+        let Some(node) = nodes.next() else {
+            continue;
+        };
 
-    for (node, connected_nodes) in network_to_save {
-        file_writer.write(node.as_bytes())?;
+        writer.write_all(node.as_bytes())?;
 
-        for connected_node in connected_nodes {
-            file_writer.write(separator)?;
-            file_writer.write(connected_node.as_bytes())?;
+        for link in without_disambiguation_links(node, nodes.collect(), disambiguation_pages, handling) {
+            writer.write_all(b"; ")?;
+            writer.write_all(link.as_bytes())?;
         }
 
-        file_writer.write(newline)?;
+        writer.write_all(b"\n")?;
     }
 
-    Ok(())
-}
-
-fn save_redirects<T>(network_to_save: T, save_file_path: PathBuf) -> Result<()> where T: Iterator<Item = (String, String)> {
-    let save_file = File::create(save_file_path)?;
-    let mut file_writer = BufWriter::with_capacity(16 * 1024 * 1024,save_file);
+    writer.flush()?;
+    drop(writer);
 
-    let separator = ";".as_bytes();
-    let newline = "\n".as_bytes();
+    remove_file(&network_file_path)?;
+    rename(temporary_network_file_path, network_file_path)
+}
 
-    for (node, target) in network_to_save {
-        file_writer.write(node.as_bytes())?;
-        file_writer.write(separator)?;
-        file_writer.write(target.as_bytes())?;
-        file_writer.write(newline)?;
+/// The links of `node` without the links to disambiguation pages. When collapsing, their links are
+/// added instead, except for links back to `node`, to other disambiguation pages and to pages
+/// `node` already links to.
+fn without_disambiguation_links<'a>(node: &str, links: Vec<&'a str>, disambiguation_pages: &'a HashMap<String, Vec<String>>, handling: DisambiguationHandling) -> Vec<&'a str> {
+    let mut linked = links.iter()
+        .filter(|link| !disambiguation_pages.contains_key(**link))
+        .copied()
+        .collect::<HashSet<&str>>();
+    let mut result = Vec::new();
+
+    for link in links {
+        match disambiguation_pages.get(link) {
+            None => result.push(link),
+            Some(targets) if handling == DisambiguationHandling::Collapse => {
+                for target in targets {
+                    if target != node && !disambiguation_pages.contains_key(target) && linked.insert(target.as_str()) {
+                        result.push(target.as_str());
+                    }
+                }
+            },
+            Some(_) => {}
+        }
     }
 
-    Ok(())
+    result
 }
 
 fn close_redirects(mut redirect_file: PathBuf) -> Result<()> {
@@ -254,4 +600,93 @@ fn resolve_redirects(redirect_file_path: PathBuf, links: HashSet<&String>) -> Re
     }
 
     return Ok(results);
-}
\ No newline at end of file
+}
+
+#[cfg(test)]
+mod tests {
+    use std::{collections::{BTreeSet, HashMap}, env::temp_dir, fs::{read_to_string, remove_dir_all, write}, io::ErrorKind, path::PathBuf};
+
+    use quick_xml::Reader;
+
+    use crate::network_generator::wiki_xml_dump::{Namespaces, WikiXmlDump};
+
+    use super::{merge_parts, save_manifest, select_namespaces, start_checkpoint, without_disambiguation_links, DisambiguationHandling};
+
+    fn test_directory(name: &str) -> PathBuf {
+        let directory = temp_dir().join(format!("wikilytics-{name}-{}", std::process::id()));
+        let _ = remove_dir_all(&directory);
+        directory
+    }
+
+    #[test]
+    fn test_select_namespaces() {
+        let siteinfo = r#"<siteinfo><namespaces><namespace key="0" /><namespace key="14">Category</namespace><namespace key="118">Draft</namespace></namespaces></siteinfo>"#;
+        let namespaces = WikiXmlDump::new(Reader::from_str(siteinfo)).read_namespaces();
+
+        assert_eq!(select_namespaces(&namespaces, &[]).unwrap(), vec![Namespaces::MAIN]);
+        assert_eq!(select_namespaces(&namespaces, &["0".to_string(), "Category".to_string(), "draft".to_string()]).unwrap(), vec![0, 14, 118]);
+        assert_eq!(select_namespaces(&namespaces, &["Portal".to_string()]).unwrap_err().kind(), ErrorKind::InvalidInput);
+        assert_eq!(select_namespaces(&namespaces, &["100".to_string()]).unwrap_err().kind(), ErrorKind::InvalidInput);
+    }
+
+    #[test]
+    fn test_without_disambiguation_links() {
+        let disambiguation_pages = HashMap::from([
+            ("Mercury".to_string(), vec!["Mercury (planet)".to_string(), "Berlin".to_string(), "Hermes".to_string(), "Mercury (element)".to_string()]),
+            ("Hermes".to_string(), vec!["Hermes (god)".to_string(), "Mercury".to_string()])
+        ]);
+        let links = vec!["Germany", "Mercury", "Mercury (planet)", "Hermes"];
+
+        assert_eq!(without_disambiguation_links("Berlin", links.clone(), &disambiguation_pages, DisambiguationHandling::Exclude),
+            vec!["Germany", "Mercury (planet)"]);
+        assert_eq!(without_disambiguation_links("Berlin", links, &disambiguation_pages, DisambiguationHandling::Collapse),
+            vec!["Germany", "Mercury (element)", "Mercury (planet)", "Hermes (god)"]);
+    }
+
+    #[test]
+    fn test_start_checkpoint() {
+        let directory = test_directory("checkpoint");
+        let parts = vec![(0, 100), (100, 200)];
+
+        let mut manifest = start_checkpoint(&directory, parts.clone(), "settings".to_string(), false).unwrap();
+        assert!(manifest.completed.is_empty());
+
+        manifest.completed.insert(1);
+        save_manifest(&directory, &manifest).unwrap();
+        write(directory.join("1.network"), "Berlin; Germany\n").unwrap();
+
+        let resumed = start_checkpoint(&directory, parts.clone(), "settings".to_string(), true).unwrap();
+        assert_eq!(resumed.completed, BTreeSet::from([1]));
+        assert!(directory.join("1.network").exists());
+
+        let other_settings = start_checkpoint(&directory, parts.clone(), "other settings".to_string(), true);
+        assert_eq!(other_settings.unwrap_err().kind(), ErrorKind::InvalidInput);
+
+        let other_dump = start_checkpoint(&directory, vec![(0, 200)], "settings".to_string(), true);
+        assert_eq!(other_dump.unwrap_err().kind(), ErrorKind::InvalidInput);
+
+        let restarted = start_checkpoint(&directory, parts, "settings".to_string(), false).unwrap();
+        assert!(restarted.completed.is_empty());
+        assert!(!directory.join("1.network").exists());
+
+        remove_dir_all(directory).unwrap();
+    }
+
+    #[test]
+    fn test_merge_parts() {
+        let directory = test_directory("merge");
+        std::fs::create_dir_all(&directory).unwrap();
+
+        let part_file_paths = (0..3).map(|part| directory.join(format!("{part}.network"))).collect::<Vec<PathBuf>>();
+        write(&part_file_paths[2], "C; A\n").unwrap();
+        write(&part_file_paths[0], "A; B\n").unwrap();
+        write(&part_file_paths[1], "").unwrap();
+
+        let merged_file_path = directory.join("network.txt");
+        merge_parts(part_file_paths.iter(), &merged_file_path).unwrap();
+
+        assert_eq!(read_to_string(&merged_file_path).unwrap(), "A; B\nC; A\n");
+
+        remove_dir_all(directory).unwrap();
+    }
+}
@@ -1,6 +1,6 @@
 use std::{collections::HashMap, io::BufRead, net, sync::mpsc::{channel, sync_channel, Sender, SyncSender, TrySendError}, thread};
 
-use self::{wiki_text::{is_redirect, linked_articles, parse_text, redirects_to}, wiki_xml_dump::{WikiPage, WikiXmlDump}};
+use self::{wiki_text::{is_redirect, linked_articles, parse_text, redirects_to}, wiki_xml_dump::{Namespaces, WikiPage, WikiXmlDump}};
 
 pub mod wiki_xml_dump;
 pub mod wiki_text;
@@ -84,7 +84,7 @@ fn spawn_page_processing_threads(
 
         thread::spawn(move || {
             for page in receiver {
-                if page.namespace_id == 0 {
+                if page.namespace_id == Namespaces::MAIN {
                     let links = parse_text(&page);
             
                     if let Some(links) = links {
@@ -115,7 +115,7 @@ fn spawn_page_processing_threads(
 }
 
 fn process_page(network: &mut HashMap<String, Vec<String>>, redirects: &mut HashMap<String, String>, page: WikiPage) {
-    if page.namespace_id == 0 {
+    if page.namespace_id == Namespaces::MAIN {
         let links = parse_text(&page);
 
         if let Some(links) = links {
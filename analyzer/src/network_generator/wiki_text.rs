@@ -1,4 +1,6 @@
-use std::cmp::min;
+use std::{cmp::min, collections::BTreeMap};
+
+use serde::Serialize;
 
 use crate::network_generator::wiki_xml_dump::WikiPage;
 
@@ -38,7 +40,10 @@ impl LinkOrRedirect {
 }
 
 pub fn parse_text(page: &WikiPage) -> Option<Vec<LinkOrRedirect>> {
-    let text = page.text.as_ref()?;
+    Some(parse_wikitext(page.text.as_ref()?))
+}
+
+fn parse_wikitext(text: &str) -> Vec<LinkOrRedirect> {
     let chars = text.chars().collect::<Vec<char>>();
     let mut index = 0;
     let mut links_or_redirects = vec![];
@@ -115,7 +120,7 @@ pub fn parse_text(page: &WikiPage) -> Option<Vec<LinkOrRedirect>> {
     }
     
 
-    Some(links_or_redirects)
+    links_or_redirects
 }
 
 pub fn linked_articles<'a>(text: &'a Vec<LinkOrRedirect>) -> Vec<&'a String> {
@@ -132,11 +137,686 @@ pub fn redirects_to<'a>(text: &'a Vec<LinkOrRedirect>) -> Option<&'a String> {
     text.iter().filter_map(|link| link.redirect_text()).next()
 }
 
+/// The prefix of categories in the category network, whatever the name of the category namespace
+/// of the dump is, so category names can be looked up the same way in every edition.
+pub const CATEGORY_PREFIX: &str = "Category:";
+
+/// The categories a page is in, written as links like `[[Category:Cities|Berlin]]`. Besides the
+/// local name of the category namespace from the siteinfo the English name always works. Links
+/// with a leading colon like `[[:Category:Cities]]` only point to the category page. Categories
+/// always get the `Category:` prefix.
+pub fn category_links(text: &[LinkOrRedirect], category_namespace: &str) -> Vec<String> {
+    text.iter()
+        .filter_map(|link| link.link_text())
+        .filter_map(|link| canonical_category_title(link, category_namespace))
+        .collect()
+}
+
+/// The title of a category with the `Category:` prefix instead of the local name of the namespace,
+/// if `title` starts with the English or the local name of the category namespace.
+pub fn canonical_category_title(title: &str, category_namespace: &str) -> Option<String> {
+    let (namespace, name) = title.split_once(':')?;
+    let namespace = namespace.trim().to_lowercase();
+
+    (namespace == category_namespace.to_lowercase() || namespace == "category")
+        .then(|| category_title(name))
+        .flatten()
+}
+
+/// `Category:` followed by the name with an uppercase first letter.
+pub fn category_title(name: &str) -> Option<String> {
+    let name = name.trim();
+    let mut letters = name.chars();
+    let first_letter = letters.next()?;
+
+    Some(format!("{CATEGORY_PREFIX}{}{}", first_letter.to_uppercase(), letters.as_str()))
+}
+
+/// Sections which usually list related or cited pages instead of linking them from the text.
+pub const EXCLUDED_SECTIONS: [(&str, &[&str]); 4] = [
+    ("en", &["See also", "References", "External links", "Further reading", "Notes", "Bibliography", "Sources"]),
+    ("de", &["Siehe auch", "Einzelnachweise", "Weblinks", "Literatur", "Anmerkungen", "Quellen"]),
+    ("fr", &["Voir aussi", "Références", "Liens externes", "Notes et références", "Bibliographie", "Articles connexes"]),
+    ("es", &["Véase también", "Referencias", "Enlaces externos", "Bibliografía", "Notas"])
+];
+
+pub fn excluded_sections(language: &str) -> Option<&'static [&'static str]> {
+    EXCLUDED_SECTIONS.iter()
+        .find(|(name, _)| *name == language)
+        .map(|(_, sections)| *sections)
+}
+
+#[derive(PartialEq, Debug)]
+pub struct Section {
+    /// Headings from the top level down to this section, empty for the lead section.
+    pub path: Vec<String>,
+    pub level: usize,
+    pub links: Vec<String>
+}
+
+impl Section {
+    /// Whether this section or one of its parents has one of the given lowercase names.
+    pub fn is_within(&self, names: &[String]) -> bool {
+        self.path.iter().any(|heading| names.contains(&heading.to_lowercase()))
+    }
+}
+
+/// Splits the text of a page at its headings and collects the links of every section.
+pub fn parse_sections(page: &WikiPage) -> Option<Vec<Section>> {
+    parse_text_with_sections(page).map(|(_, sections)| sections)
+}
+
+/// Parses the text of a page once and returns both the links and redirects of the whole page, like
+/// `parse_text`, and the links of every section. Links in a heading belong to the section the
+/// heading opens.
+pub fn parse_text_with_sections(page: &WikiPage) -> Option<(Vec<LinkOrRedirect>, Vec<Section>)> {
+    let text = page.text.as_ref()?;
+    let mut links = Vec::new();
+    let mut sections = Vec::new();
+    let mut path: Vec<(usize, String)> = Vec::new();
+    let mut body_start = 0;
+    let mut offset = 0;
+
+    for line in text.split_inclusive('\n') {
+        let trimmed_line = line.trim();
+
+        if let Some(heading) = heading(trimmed_line) {
+            sections.push(section(&path, &text[body_start..offset], &mut links));
+
+            let level = trimmed_line.chars().take_while(|c| *c == '=').count();
+            path.retain(|(parent_level, _)| *parent_level < level);
+            path.push((level, render_inline(heading).trim().to_owned()));
+
+            body_start = offset;
+        }
+
+        offset += line.len();
+    }
+
+    sections.push(section(&path, &text[body_start..], &mut links));
+
+    Some((links, sections))
+}
+
+/// The section with the links of `body`, all links and redirects of it are added to `links`.
+fn section(path: &[(usize, String)], body: &str, links: &mut Vec<LinkOrRedirect>) -> Section {
+    let body_links = parse_wikitext(body);
+
+    let section = Section {
+        path: path.iter().map(|(_, heading)| heading.to_owned()).collect(),
+        level: path.last().map_or(0, |(level, _)| *level),
+        links: body_links.iter()
+            .filter_map(|link| link.link_text().cloned())
+            .collect()
+    };
+
+    links.extend(body_links);
+
+    section
+}
+
+/// The title in the other edition if `link` is an interlanguage link like `[[de:Berlin]]` to
+/// `language`. Links with a leading colon are inline interwiki links and no interlanguage links.
+pub fn interlanguage_link<'a>(link: &'a str, language: &str) -> Option<&'a str> {
+    let (prefix, title) = link.split_once(':')?;
+
+    if prefix.trim().eq_ignore_ascii_case(language) && !title.trim().is_empty() {
+        Some(title.trim())
+    } else {
+        None
+    }
+}
+
+/// The text of a page without comments, templates, `<ref>`s, html tags and bold or italic quotes.
+/// Every link is replaced by a single space, so nothing in the result is part of a link.
+pub fn unlinked_text(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            result.push(' ');
+        } else if rest.starts_with("{{") {
+            rest = skip_nested(rest, "{{", "}}");
+            result.push(' ');
+        } else if rest.starts_with("[[") {
+            rest = skip_nested(rest, "[[", "]]");
+            result.push(' ');
+        } else if starts_with_tag(rest, "ref") {
+            let tag_end = rest.find('>').map_or(rest.len(), |end| end + 1);
+
+            rest = if rest[..tag_end].ends_with("/>") {
+                &rest[tag_end..]
+            } else {
+                rest.find("</ref>").map_or("", |end| &rest[end + 6..])
+            };
+            result.push(' ');
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if rest.starts_with("''") {
+            rest = rest.trim_start_matches('\'');
+        } else {
+            result.push(character);
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+
+    result
+}
+
+fn starts_with_tag(text: &str, name: &str) -> bool {
+    text.strip_prefix('<')
+        .and_then(|text| text.strip_prefix(name))
+        .is_some_and(|text| text.starts_with(['>', ' ', '/']))
+}
+
+/// The text after the `close` token matching the `open` token `text` starts with.
+fn skip_nested<'a>(text: &'a str, open: &str, close: &str) -> &'a str {
+    let mut depth = 0;
+    let mut rest = text;
+
+    while !rest.is_empty() {
+        if rest.starts_with(open) {
+            depth += 1;
+            rest = &rest[open.len()..];
+        } else if rest.starts_with(close) {
+            depth -= 1;
+            rest = &rest[close.len()..];
+
+            if depth == 0 {
+                return rest;
+            }
+        } else {
+            let character = rest.chars().next().unwrap();
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+
+    rest
+}
+
+/// Tags which are dropped together with everything between the opening and the closing tag.
+const DROPPED_ELEMENTS: [&str; 5] = ["ref", "gallery", "math", "timeline", "syntaxhighlight"];
+
+/// The readable text of a page. Templates, tables, comments, references and files are dropped,
+/// links are replaced by their labels and headings are kept as lines of their own.
+pub fn plain_text(page: &WikiPage) -> Option<String> {
+    let text = render_inline(page.text.as_ref()?);
+    let mut lines: Vec<String> = Vec::new();
+
+    for line in text.lines() {
+        let line = line.trim();
+
+        if let Some(heading) = heading(line) {
+            lines.push(String::new());
+            lines.push(heading.to_owned());
+            lines.push(String::new());
+        } else if line.chars().all(|c| c == '-') && line.len() >= 4 {
+            lines.push(String::new());
+        } else {
+            lines.push(line.trim_start_matches(['*', '#', ':', ';']).trim_start().to_owned());
+        }
+    }
+
+    let mut result = String::with_capacity(text.len());
+    let mut previous_empty = true;
+
+    for line in lines {
+        if line.is_empty() {
+            if !previous_empty {
+                result.push('\n');
+            }
+        } else {
+            result.push_str(&line);
+            result.push('\n');
+        }
+
+        previous_empty = line.is_empty();
+    }
+
+    Some(result.trim_end().to_owned())
+}
+
+/// The text of a heading line like `== History ==`.
+fn heading(line: &str) -> Option<&str> {
+    let level = line.chars().take_while(|c| *c == '=').count();
+
+    if level == 0 || line.len() <= 2 * level || !line.ends_with(&"=".repeat(level)) {
+        return None;
+    }
+
+    Some(line[level..line.len() - level].trim())
+}
+
+fn render_inline(text: &str) -> String {
+    let mut result = String::with_capacity(text.len());
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+        } else if rest.starts_with("{{") {
+            rest = skip_nested(rest, "{{", "}}");
+        } else if rest.starts_with("{|") {
+            rest = skip_nested(rest, "{|", "|}");
+        } else if rest.starts_with("[[") {
+            let after = skip_nested(rest, "[[", "]]");
+            let link = rest[..rest.len() - after.len()].trim_start_matches("[[").trim_end_matches("]]");
+
+            result.push_str(&render_link(link));
+            rest = after;
+        } else if rest.starts_with("[http") || rest.starts_with("[//") {
+            let end = rest.find(']').unwrap_or(rest.len());
+
+            if let Some((_, label)) = rest[1..end].split_once(' ') {
+                result.push_str(&render_inline(label));
+            }
+            rest = rest.get(end + 1..).unwrap_or("");
+        } else if let Some(name) = DROPPED_ELEMENTS.iter().find(|name| starts_with_tag(rest, name)) {
+            let tag_end = rest.find('>').map_or(rest.len(), |end| end + 1);
+            let closing_tag = format!("</{name}>");
+
+            rest = if rest[..tag_end].ends_with("/>") {
+                &rest[tag_end..]
+            } else {
+                rest.find(&closing_tag).map_or("", |end| &rest[end + closing_tag.len()..])
+            };
+        } else if rest.starts_with('<') && rest[1..].starts_with(|c: char| c.is_ascii_alphabetic() || c == '/') {
+            rest = rest.find('>').map_or("", |end| &rest[end + 1..]);
+        } else if rest.starts_with("''") {
+            rest = rest.trim_start_matches('\'');
+        } else if let Some(after) = magic_word_end(rest) {
+            rest = after;
+        } else {
+            result.push(character);
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+
+    result
+}
+
+/// The label of a link. Links into other namespaces like files, categories or other languages
+/// have no label in the text, titles like `Star Trek: Voyager` are no namespaces.
+fn render_link(link: &str) -> String {
+    let (target, label) = match link.split_once('|') {
+        Some((target, label)) => (target, Some(label)),
+        None => (link, None)
+    };
+
+    if target.split_once(':').is_some_and(|(prefix, _)| is_namespace_prefix(prefix)) {
+        return String::new();
+    }
+
+    match label {
+        Some(label) if !label.trim().is_empty() => render_inline(label),
+        _ => target.trim_start_matches(':').trim().to_owned()
+    }
+}
+
+/// Canonical namespaces, interwiki prefixes and the file and category namespaces of the languages
+/// in `EXCLUDED_SECTIONS`, in lowercase. Talk namespaces are matched by their subject namespace.
+const NAMESPACE_PREFIXES: [&str; 36] = [
+    "media", "special", "talk", "user", "wikipedia", "wp", "project", "file", "image", "mediawiki",
+    "template", "help", "category", "portal", "draft", "module", "timedtext", "simple",
+    "wiktionary", "wikt", "commons", "wikisource", "wikiquote", "wikibooks", "wikinews", "wikidata",
+    "d", "w", "m", "meta", "datei", "bild", "kategorie", "fichier", "catégorie", "categoría"
+];
+
+/// Whether the part of a link before the first colon is a namespace, an interwiki prefix or a
+/// language code like `de` or `zh-min-nan`.
+fn is_namespace_prefix(prefix: &str) -> bool {
+    let prefix = prefix.trim();
+
+    if prefix.is_empty() {
+        return false;
+    }
+
+    let lowercase_prefix = prefix.to_lowercase();
+    let namespace = lowercase_prefix.strip_suffix(" talk").unwrap_or(&lowercase_prefix);
+
+    if NAMESPACE_PREFIXES.contains(&namespace) {
+        return true;
+    }
+
+    let mut parts = prefix.split('-');
+
+    parts.next().is_some_and(|language| (2..=3).contains(&language.len()) && language.chars().all(|c| c.is_ascii_lowercase()))
+        && parts.all(|part| !part.is_empty() && part.chars().all(|c| c.is_ascii_lowercase()))
+}
+
+/// The text after a behavior switch like `__TOC__`, if `text` starts with one.
+fn magic_word_end(text: &str) -> Option<&str> {
+    let name = text.strip_prefix("__")?;
+    let length = name.chars().take_while(|c| c.is_ascii_uppercase()).count();
+
+    (length > 0).then(|| name[length..].strip_prefix("__")).flatten()
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct Template {
+    pub name: String,
+    /// Named parameters by name and positional parameters by their position starting at 1.
+    pub params: BTreeMap<String, TemplateValue>
+}
+
+#[derive(Serialize, PartialEq, Debug)]
+pub struct TemplateValue {
+    pub text: String,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub links: Vec<String>,
+    #[serde(skip_serializing_if = "Vec::is_empty")]
+    pub templates: Vec<Template>
+}
+
+impl Template {
+    /// Whether the name of the template starts with one of the given lowercase prefixes.
+    pub fn has_prefix(&self, prefixes: &[String]) -> bool {
+        let name = self.name.to_lowercase();
+        prefixes.iter().any(|prefix| name.starts_with(prefix))
+    }
+}
+
+/// The templates in a text which are not part of other templates. Parser functions and magic words
+/// like `{{#if:}}` or `{{DEFAULTSORT:}}` are no templates.
+pub fn parse_templates(text: &str) -> Vec<Template> {
+    let mut templates = Vec::new();
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+        } else if rest.starts_with("{{") {
+            let after = skip_nested(rest, "{{", "}}");
+            let inner = rest[..rest.len() - after.len()].trim_start_matches("{{").trim_end_matches("}}");
+
+            templates.extend(parse_template(inner));
+            rest = after;
+        } else {
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+
+    templates
+}
+
+fn parse_template(inner: &str) -> Option<Template> {
+    let mut parts = split_top_level(inner, '|').into_iter();
+
+    let name = parts.next()?.trim().replace('_', " ");
+    let name = name.strip_prefix("Template:").unwrap_or(&name).trim();
+
+    if name.is_empty() || name.starts_with('#') || name.contains(':') {
+        return None;
+    }
+
+    let mut params = BTreeMap::new();
+    let mut position = 0;
+
+    for part in parts {
+        let (key, value) = match split_top_level(part, '=').as_slice() {
+            [] | [_] => {
+                position += 1;
+                (position.to_string(), part)
+            },
+            [key, ..] => (key.trim().to_owned(), &part[key.len() + 1..])
+        };
+
+        params.insert(key, TemplateValue {
+            text: render_inline(value).trim().to_owned(),
+            links: parse_wikitext(value).into_iter()
+                .filter_map(|link| match link {
+                    LinkOrRedirect::Link(link) => Some(link),
+                    LinkOrRedirect::Redirect(_) => None
+                })
+                .collect(),
+            templates: parse_templates(value)
+        });
+    }
+
+    Some(Template { name: name.to_owned(), params })
+}
+
+/// Splits `text` at every `separator` outside of templates and links.
+fn split_top_level(text: &str, separator: char) -> Vec<&str> {
+    let mut parts = Vec::new();
+    let mut depth = 0usize;
+    let mut part_start = 0;
+    let mut index = 0;
+
+    while index < text.len() {
+        let rest = &text[index..];
+
+        if rest.starts_with("{{") || rest.starts_with("[[") {
+            depth += 1;
+            index += 2;
+        } else if rest.starts_with("}}") || rest.starts_with("]]") {
+            depth = depth.saturating_sub(1);
+            index += 2;
+        } else {
+            let character = rest.chars().next().unwrap();
+
+            if character == separator && depth == 0 {
+                parts.push(&text[part_start..index]);
+                part_start = index + 1;
+            }
+
+            index += character.len_utf8();
+        }
+    }
+
+    parts.push(&text[part_start..]);
+    parts
+}
+
+const URL_SCHEMES: [&str; 3] = ["http://", "https://", "ftp://"];
+
+/// The external links of a text, both bracketed ones like `[http://example.org label]` and bare
+/// URLs, as they are written.
+pub fn external_links(text: &str) -> Vec<&str> {
+    let mut links = Vec::new();
+    let mut rest = text;
+
+    while let Some(character) = rest.chars().next() {
+        if rest.starts_with("<!--") {
+            rest = rest.find("-->").map_or("", |end| &rest[end + 3..]);
+            continue;
+        }
+
+        let bracketed = rest.strip_prefix('[')
+            .filter(|url| url.starts_with("//") || starts_with_url(url));
+        let preceded_by_word = text[..text.len() - rest.len()].chars().next_back().is_some_and(char::is_alphanumeric);
+
+        if let Some(url) = bracketed {
+            let end = url.find(|c: char| c.is_whitespace() || c == ']' || c == '<').unwrap_or(url.len());
+            links.push(&url[..end]);
+            rest = &url[end..];
+        } else if starts_with_url(rest) && !preceded_by_word {
+            let end = rest.find(|c: char| c.is_whitespace() || "|]}<\"".contains(c)).unwrap_or(rest.len());
+            links.push(rest[..end].trim_end_matches(['.', ',', ';', ':', '!', '?', '\'', ')']));
+            rest = &rest[end..];
+        } else {
+            rest = &rest[character.len_utf8()..];
+        }
+    }
+
+    links
+}
+
+fn starts_with_url(text: &str) -> bool {
+    URL_SCHEMES.iter().any(|scheme| text.len() > scheme.len()
+        && text.get(..scheme.len()).is_some_and(|prefix| prefix.eq_ignore_ascii_case(scheme)))
+}
+
+/// The URL with lowercase scheme and host, without fragment, user info and default port.
+/// Protocol relative URLs are assumed to use https.
+pub fn normalize_url(url: &str) -> Option<String> {
+    let (scheme, rest) = match url.strip_prefix("//") {
+        Some(rest) => ("https".to_owned(), rest),
+        None => url.split_once("://").map(|(scheme, rest)| (scheme.to_lowercase(), rest))?
+    };
+
+    let rest = rest.split('#').next().unwrap_or_default();
+    let authority_end = rest.find(['/', '?']).unwrap_or(rest.len());
+    let (authority, path) = rest.split_at(authority_end);
+
+    let host = authority.rsplit('@').next().unwrap_or_default().to_lowercase();
+    let host = match (scheme.as_str(), host.rsplit_once(':')) {
+        ("http", Some((host, "80"))) | ("https", Some((host, "443"))) => host.to_owned(),
+        _ => host
+    };
+    let host = host.trim_end_matches('.');
+
+    if host.is_empty() {
+        return None;
+    }
+
+    let path = if path.is_empty() { "/" } else { path };
+
+    Some(format!("{scheme}://{host}{path}"))
+}
+
+/// The host of a normalized URL without port and leading `www.`.
+pub fn url_domain(url: &str) -> Option<&str> {
+    let (_, rest) = url.split_once("://")?;
+    let host = rest.split(['/', '?']).next()?;
+    let host = host.split(':').next()?;
+
+    Some(host.strip_prefix("www.").unwrap_or(host))
+}
+
+#[derive(Serialize, PartialEq, Debug, Default)]
+#[serde(rename_all = "camelCase")]
+pub struct Citation {
+    /// Name of the citation template, none for references with free text.
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub template: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub url: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub doi: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub isbn: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub title: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub publisher: Option<String>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    pub date: Option<String>
+}
+
+impl Citation {
+    fn from_template(template: &Template) -> Citation {
+        let param = |names: &[&str]| names.iter()
+            .filter_map(|name| template.params.get(*name))
+            .map(|value| value.text.to_owned())
+            .find(|text| !text.is_empty());
+
+        Citation {
+            template: Some(template.name.to_owned()),
+            url: param(&["url", "chapter-url"]),
+            doi: param(&["doi"]),
+            isbn: param(&["isbn", "ISBN"]),
+            title: param(&["title", "chapter"]),
+            publisher: param(&["publisher", "work", "website", "newspaper", "journal", "magazine"]),
+            date: param(&["date", "year"])
+        }
+    }
+}
+
+/// Lowercase names of templates marking claims without a source, which are no citations.
+const CITATION_NEEDED_TEMPLATES: [&str; 4] = ["citation needed", "cn", "fact", "cite needed"];
+
+/// Whether the template is `{{citation}}` or one of the `{{cite …}}` templates.
+fn is_citation_template(template: &Template) -> bool {
+    let name = template.name.to_lowercase().replace('_', " ");
+    let name = name.trim();
+
+    if CITATION_NEEDED_TEMPLATES.contains(&name) {
+        return false;
+    }
+
+    name == "citation" || name == "cite" || name.starts_with("cite ")
+}
+
+/// The citations of a text. These are all citation templates like `{{cite web}}` and every
+/// `<ref>` without such a template. References reusing a named reference like `<ref name="a"/>`
+/// are no new citations.
+pub fn parse_citations(text: &str) -> Vec<Citation> {
+    parse_citations_with_templates(text, &parse_templates(text))
+}
+
+/// Like `parse_citations` for a text whose templates were already parsed.
+pub fn parse_citations_with_templates(text: &str, templates: &[Template]) -> Vec<Citation> {
+    let mut citations = templates.iter()
+        .filter(|template| is_citation_template(template))
+        .map(Citation::from_template)
+        .collect::<Vec<Citation>>();
+
+    let mut rest = text;
+
+    while let Some(start) = rest.find("<ref") {
+        rest = &rest[start..];
+
+        if !starts_with_tag(rest, "ref") {
+            rest = &rest[4..];
+            continue;
+        }
+
+        let tag_end = rest.find('>').map_or(rest.len(), |end| end + 1);
+
+        if rest[..tag_end].ends_with("/>") {
+            rest = &rest[tag_end..];
+            continue;
+        }
+
+        let body_end = rest.find("</ref>").unwrap_or(rest.len());
+        let body = &rest[tag_end.min(body_end)..body_end];
+
+        if !parse_templates(body).iter().any(is_citation_template) {
+            citations.push(Citation {
+                url: external_links(body).into_iter().find_map(normalize_url),
+                title: Some(render_inline(body).trim().to_owned()).filter(|title| !title.is_empty()),
+                ..Citation::default()
+            });
+        }
+
+        rest = &rest[body_end..];
+    }
+
+    citations
+}
+
+/// Lowercase names of templates marking disambiguation pages. Templates whose name ends with
+/// `disambiguation`, like `{{Human name disambiguation}}`, count as well.
+const DISAMBIGUATION_TEMPLATES: [&str; 10] = [
+    "disambiguation", "disambig", "disamb", "dab", "dis", "hndis", "geodis", "numberdis",
+    "begriffsklärung", "homonymie"
+];
+
+/// Whether the text is the text of a disambiguation page, which is marked by the behavior switch
+/// `__DISAMBIG__` or by one of the disambiguation templates among the `templates` of the text.
+pub fn is_disambiguation(text: &str, templates: &[Template]) -> bool {
+    text.contains("__DISAMBIG__") || templates.iter().any(|template| {
+        let name = template.name.to_lowercase();
+        DISAMBIGUATION_TEMPLATES.contains(&name.as_str()) || name.ends_with(" disambiguation")
+    })
+}
+
+/// Whether the templates of a text mark a stub, like `{{Physics-stub}}`.
+pub fn is_stub(templates: &[Template]) -> bool {
+    templates.iter().any(|template| {
+        let name = template.name.to_lowercase();
+        name == "stub" || name.ends_with("-stub")
+    })
+}
+
 #[cfg(test)]
 mod tests {
     use crate::network_generator::{wiki_text::LinkOrRedirect, wiki_xml_dump::WikiPage};
 
-    use super::parse_text;
+    use super::{canonical_category_title, category_links, external_links, interlanguage_link, is_disambiguation, is_stub, normalize_url, parse_citations, parse_sections, parse_templates, parse_text, parse_text_with_sections, plain_text, unlinked_text, url_domain, Citation, Section};
 
 
     #[test]
@@ -321,4 +1001,165 @@ mod tests {
 
         assert_eq!(parse_result, Some(vec![]));
     }
-}
\ No newline at end of file
+
+    #[test]
+    fn test_interlanguage_link() {
+        assert_eq!(interlanguage_link("de:Berlin", "de"), Some("Berlin"));
+        assert_eq!(interlanguage_link("DE: Berlin", "de"), Some("Berlin"));
+        assert_eq!(interlanguage_link(":de:Berlin", "de"), None);
+        assert_eq!(interlanguage_link("fr:Berlin", "de"), None);
+        assert_eq!(interlanguage_link("Berlin", "de"), None);
+    }
+
+    #[test]
+    fn test_category_links() {
+        let links = vec![
+            LinkOrRedirect::Link("Categoria:città d'Italia".to_string()),
+            LinkOrRedirect::Link("category:Capitals".to_string()),
+            LinkOrRedirect::Link(":Categoria:Fiumi".to_string()),
+            LinkOrRedirect::Link("Kategorie:Stadt".to_string()),
+            LinkOrRedirect::Link("Roma".to_string())
+        ];
+
+        assert_eq!(category_links(&links, "Categoria"), vec!["Category:Città d'Italia".to_string(), "Category:Capitals".to_string()]);
+        assert_eq!(canonical_category_title("CATEGORIA: Fiumi", "Categoria"), Some("Category:Fiumi".to_string()));
+        assert_eq!(canonical_category_title("Categoria:Fiumi", "Category"), None);
+    }
+
+    #[test]
+    fn test_unlinked_text() {
+        let text = "'''Berlin''' is the [[capital city|capital]]{{efn|of {{Germany}}}} of Germany.<ref name=\"a\">[[Source]]</ref><ref name=\"b\"/><!-- [[Comment]] --><br/>";
+
+        assert_eq!(unlinked_text(text), "Berlin is the    of Germany.   ");
+    }
+
+    #[test]
+    fn test_plain_text() {
+        let test_page = WikiPage{
+            namespace_id: 0,
+            text: Some("{{Infobox city\n| name = Berlin\n}}\n'''Berlin''' is the [[capital city|capital]] of [[Germany]].<ref>{{cite web|url=x}}</ref>\n[[File:Berlin.jpg|thumb|A [[view]]]]\n== History ==\n* Founded in [[1237]]\n{|\n| table\n|}\n[[Category:Cities]]".to_string()),
+            title: "Berlin".to_string()
+        };
+
+        assert_eq!(plain_text(&test_page), Some("Berlin is the capital of Germany.\n\nHistory\n\nFounded in 1237".to_string()));
+    }
+
+    #[test]
+    fn test_plain_text_of_titles_with_colons() {
+        let test_page = WikiPage{
+            namespace_id: 0,
+            text: Some("[[Star Trek: Voyager]] and [[Mission: Impossible|the film]] [[de:Star Trek: Raumschiff Voyager]][[zh-min-nan:Star Trek]][[Wikipedia:About]][[User talk:Example]][[Kategorie:Film]][[:Category:Films]]".to_string()),
+            title: "Test".to_string()
+        };
+
+        assert_eq!(plain_text(&test_page), Some("Star Trek: Voyager and the film Category:Films".to_string()));
+    }
+
+    #[test]
+    fn test_parse_sections() {
+        let test_page = WikiPage{
+            namespace_id: 0,
+            text: Some("Lead [[A]]\n== History ==\n[[B]]\n=== Early [[history]] ===\n[[C]] [[D]]\n== See also ==\n[[E]]".to_string()),
+            title: "Test".to_string()
+        };
+
+        let sections = parse_sections(&test_page).unwrap();
+
+        assert_eq!(sections.len(), 4);
+        assert_eq!(sections[2], Section {
+            path: vec!["History".to_string(), "Early history".to_string()],
+            level: 3,
+            links: vec!["history".to_string(), "C".to_string(), "D".to_string()]
+        });
+        assert!(sections[3].is_within(&["see also".to_string()]));
+        assert!(!sections[0].is_within(&["see also".to_string()]));
+
+        let (links, _) = parse_text_with_sections(&test_page).unwrap();
+        assert_eq!(links, parse_text(&test_page).unwrap());
+    }
+
+    #[test]
+    fn test_parse_templates() {
+        let templates = parse_templates("{{Infobox city\n| name = [[Berlin]]\n| leader = {{Nowrap|Kai [[Wegner]]}}\n| Germany\n}} {{DEFAULTSORT:Berlin}}");
+
+        assert_eq!(templates.len(), 1);
+        assert_eq!(templates[0].name, "Infobox city");
+        assert_eq!(templates[0].params["name"].text, "Berlin");
+        assert_eq!(templates[0].params["name"].links, vec!["Berlin".to_string()]);
+        assert_eq!(templates[0].params["leader"].templates[0].params["1"].links, vec!["Wegner".to_string()]);
+        assert_eq!(templates[0].params["1"].text, "Germany");
+    }
+
+    #[test]
+    fn test_external_links() {
+        let text = "See [https://WWW.Example.org:443/a#top Example], http://news.example.com/x?id=1. <!-- http://hidden.org --> {{cite web|url=http://bbc.co.uk/news}} [//bbc.co.uk/sport]";
+        let links = external_links(text);
+
+        assert_eq!(links, vec!["https://WWW.Example.org:443/a#top", "http://news.example.com/x?id=1", "http://bbc.co.uk/news", "//bbc.co.uk/sport"]);
+
+        let normalized = links.into_iter().filter_map(normalize_url).collect::<Vec<String>>();
+
+        assert_eq!(normalized, vec!["https://www.example.org/a", "http://news.example.com/x?id=1", "http://bbc.co.uk/news", "https://bbc.co.uk/sport"]);
+        assert_eq!(url_domain(&normalized[0]), Some("example.org"));
+    }
+
+    #[test]
+    fn test_external_links_in_non_ascii_text() {
+        let text = "Café München liegt an der Isar, siehe https://de.wikipedia.org/wiki/München und [http://stadt.de Stadtportal «München»] für Ñandú.";
+
+        assert_eq!(external_links(text), vec!["https://de.wikipedia.org/wiki/München", "http://stadt.de"]);
+        assert!(external_links("日本語のテキスト").is_empty());
+    }
+
+    #[test]
+    fn test_parse_citations() {
+        let text = "A<ref name=\"a\">{{cite journal|title=On [[Graphs]]|journal=Nature|doi=10.1000/1|year=1999}}</ref> \
+            B<ref>[https://example.org/report Report] by the ministry</ref> C<ref name=\"a\"/> {{Citation|isbn=978-3-16}} \
+            D{{Citation needed|date=May 2020}} E{{cn}} F{{Citation Style documentation}}";
+
+        let citations = parse_citations(text);
+
+        assert_eq!(citations.len(), 3);
+        assert_eq!(citations[0], Citation {
+            template: Some("cite journal".to_string()),
+            doi: Some("10.1000/1".to_string()),
+            title: Some("On Graphs".to_string()),
+            publisher: Some("Nature".to_string()),
+            date: Some("1999".to_string()),
+            ..Citation::default()
+        });
+        assert_eq!(citations[1].isbn, Some("978-3-16".to_string()));
+        assert_eq!(citations[2].url, Some("https://example.org/report".to_string()));
+        assert_eq!(citations[2].title, Some("Report by the ministry".to_string()));
+    }
+
+    #[test]
+    fn test_parse_citations_in_non_ascii_text() {
+        let citations = parse_citations("Köln<ref>Müller, J. ''Die Stadt am Rhein.'' Köln 1990, https://bücher.example.de/köln S. 12–14.</ref>");
+
+        assert_eq!(citations, vec![Citation {
+            url: Some("https://bücher.example.de/köln".to_string()),
+            title: Some("Müller, J. Die Stadt am Rhein. Köln 1990, https://bücher.example.de/köln S. 12–14.".to_string()),
+            ..Citation::default()
+        }]);
+    }
+
+    #[test]
+    fn test_is_disambiguation() {
+        let is_disambiguation = |text: &str| is_disambiguation(text, &parse_templates(text));
+
+        assert!(is_disambiguation("'''Mercury''' may refer to:\n* [[Mercury (planet)]]\n{{disambiguation|geo}}"));
+        assert!(is_disambiguation("{{Human name disambiguation}}"));
+        assert!(is_disambiguation("__DISAMBIG__"));
+        assert!(!is_disambiguation("{{Infobox planet}} See [[Mercury (disambiguation)]]."));
+    }
+
+    #[test]
+    fn test_is_stub() {
+        let is_stub = |text: &str| is_stub(&parse_templates(text));
+
+        assert!(is_stub("'''Spandau''' is a district. {{Berlin-geo-stub}}"));
+        assert!(is_stub("{{stub}}"));
+        assert!(!is_stub("{{Infobox district}} See {{Stubbs}}."));
+    }
+}
@@ -1,3 +1,4 @@
+use std::collections::BTreeMap;
 use std::io::{BufRead, BufReader, Error, ErrorKind, Read};
 use quick_xml::reader::Reader;
 use quick_xml::events::Event;
@@ -15,6 +16,39 @@ pub struct WikiPage {
     pub namespace_id: i16
 }
 
+/// The namespaces of a wiki by id, as listed in the siteinfo at the start of a dump.
+#[derive(Debug, Clone, Default, PartialEq)]
+pub struct Namespaces {
+    names: BTreeMap<i16, String>
+}
+
+impl Namespaces {
+    pub const MAIN: i16 = 0;
+    pub const CATEGORY: i16 = 14;
+
+    pub fn name(&self, id: i16) -> Option<&str> {
+        self.names.get(&id).map(|name| name.as_str())
+    }
+
+    /// The id of a namespace given by its number or by its name. The main namespace has no name
+    /// in the siteinfo and is called `main` here.
+    pub fn id(&self, namespace: &str) -> Option<i16> {
+        let namespace = namespace.trim();
+
+        if let Ok(id) = namespace.parse::<i16>() {
+            return (self.names.is_empty() || self.names.contains_key(&id)).then_some(id);
+        }
+
+        if namespace.eq_ignore_ascii_case("main") {
+            return Some(Namespaces::MAIN);
+        }
+
+        self.names.iter()
+            .find(|(_, name)| name.eq_ignore_ascii_case(namespace))
+            .map(|(id, _)| *id)
+    }
+}
+
 impl<R: BufRead> WikiXmlDump<R> {
     pub fn new(reader: Reader<R>) -> WikiXmlDump<R> {
         WikiXmlDump {
@@ -42,6 +76,54 @@ impl<R: BufRead> WikiXmlDump<R> {
 
         None
     }
+
+    /// Reads the namespaces from the siteinfo, which has to come next in the dump.
+    pub fn read_namespaces(&mut self) -> Namespaces {
+        let mut buf = Vec::new();
+        let mut names = BTreeMap::new();
+        let mut current_key: Option<i16> = None;
+
+        let namespace_key = |e: &quick_xml::events::BytesStart| e.try_get_attribute("key")
+            .ok()
+            .flatten()
+            .and_then(|key| String::from_utf8_lossy(&key.value).parse::<i16>().ok());
+
+        loop {
+            match self.reader.read_event_into(&mut buf) {
+                Err(e) => panic!("Error at position {}: {:?}", self.reader.buffer_position(), e),
+                Ok(Event::Eof) => break,
+                Ok(Event::Empty(e)) if e.name().as_ref() == b"namespace" => {
+                    if let Some(key) = namespace_key(&e) {
+                        names.insert(key, String::new());
+                    }
+                }
+                Ok(Event::Start(e)) if e.name().as_ref() == b"namespace" => {
+                    current_key = namespace_key(&e);
+
+                    if let Some(key) = current_key {
+                        names.insert(key, String::new());
+                    }
+                }
+                Ok(Event::Text(e)) => {
+                    if let Some(key) = current_key {
+                        names.insert(key, e.unescape().unwrap().into_owned());
+                    }
+                }
+                Ok(Event::End(e)) => {
+                    match e.name().as_ref() {
+                        b"namespace" => current_key = None,
+                        b"namespaces" | b"siteinfo" => break,
+                        _ => ()
+                    }
+                }
+                _ => (),
+            }
+
+            buf.clear();
+        }
+
+        Namespaces { names }
+    }
 }
 
 fn read_page<R: BufRead>(reader: &mut Reader<R>) -> Option<WikiPage> {
@@ -122,6 +204,86 @@ impl<R: BufRead> Iterator for WikiXmlDump<R> {
     }
 }
 
+/// A single revision of a page from a history dump.
+#[derive(Debug)]
+pub struct WikiRevision {
+    pub title: String,
+    pub namespace_id: i16,
+    pub timestamp: String,
+    /// The text is missing for revisions whose content was suppressed.
+    pub text: Option<String>
+}
+
+/// Reads the revisions of a history dump one by one, so pages with long histories are never
+/// held in memory at once. The revisions of a page come in the order of the dump, which is
+/// chronological.
+pub struct WikiRevisions<R> {
+    reader: Reader<R>,
+    title: String,
+    namespace_id: i16
+}
+
+impl<R: BufRead> WikiRevisions<R> {
+    pub fn new(reader: Reader<R>) -> WikiRevisions<R> {
+        WikiRevisions { reader, title: String::new(), namespace_id: 0 }
+    }
+}
+
+impl<R: BufRead> Iterator for WikiRevisions<R> {
+    type Item = WikiRevision;
+
+    fn next(&mut self) -> Option<Self::Item> {
+        let mut buf = Vec::new();
+        let mut element: Option<Vec<u8>> = None;
+        let mut in_revision = false;
+        let mut timestamp = String::new();
+        let mut text = None;
+
+        loop {
+            match self.reader.read_event_into(&mut buf) {
+                Err(e) => panic!("Error at position {}: {:?}", self.reader.buffer_position(), e),
+                Ok(Event::Eof) => return None,
+                Ok(Event::Start(e)) => {
+                    if e.name().as_ref() == b"revision" {
+                        in_revision = true;
+                    }
+
+                    element = Some(e.name().as_ref().to_vec());
+                }
+                Ok(Event::Text(e)) => {
+                    let value = || e.unescape().unwrap().into_owned();
+
+                    match element.as_deref() {
+                        Some(b"title") if !in_revision => self.title = value(),
+                        Some(b"ns") if !in_revision => self.namespace_id = value().parse::<i16>().unwrap_or(0),
+                        Some(b"timestamp") if in_revision => timestamp = value(),
+                        Some(b"text") if in_revision => text = Some(value()),
+                        _ => ()
+                    }
+                }
+                Ok(Event::Empty(e)) if in_revision && e.name().as_ref() == b"text" => {
+                    text = e.try_get_attribute("deleted").ok().flatten().is_none().then(String::new);
+                }
+                Ok(Event::End(e)) => {
+                    element = None;
+
+                    if e.name().as_ref() == b"revision" {
+                        return Some(WikiRevision {
+                            title: self.title.to_owned(),
+                            namespace_id: self.namespace_id,
+                            timestamp,
+                            text
+                        });
+                    }
+                }
+                _ => (),
+            }
+
+            buf.clear();
+        }
+    }
+}
+
 pub type WikiIndex = Vec<WikiIndexEntry>;
 
 #[derive(Debug)]
@@ -161,3 +323,92 @@ pub fn blocks(index: WikiIndex) -> Vec<u64> {
         .collect()
 }
 
+#[cfg(test)]
+mod tests {
+    use quick_xml::Reader;
+
+    use super::{Namespaces, WikiRevisions, WikiXmlDump};
+
+    const SITEINFO: &str = r#"<mediawiki>
+  <siteinfo>
+    <sitename>Wikipedia</sitename>
+    <namespaces>
+      <namespace key="-1" case="first-letter">Spezial</namespace>
+      <namespace key="0" case="first-letter" />
+      <namespace key="14" case="first-letter">Kategorie</namespace>
+      <namespace key="100" case="first-letter">Portal</namespace>
+    </namespaces>
+  </siteinfo>
+  <page>
+    <title>Berlin</title>
+  </page>
+</mediawiki>"#;
+
+    fn namespaces() -> Namespaces {
+        WikiXmlDump::new(Reader::from_str(SITEINFO)).read_namespaces()
+    }
+
+    #[test]
+    fn test_read_namespaces() {
+        let namespaces = namespaces();
+
+        assert_eq!(namespaces.name(Namespaces::MAIN), Some(""));
+        assert_eq!(namespaces.name(Namespaces::CATEGORY), Some("Kategorie"));
+        assert_eq!(namespaces.name(-1), Some("Spezial"));
+        assert_eq!(namespaces.name(1), None);
+    }
+
+    #[test]
+    fn test_namespace_id() {
+        let namespaces = namespaces();
+
+        assert_eq!(namespaces.id("0"), Some(Namespaces::MAIN));
+        assert_eq!(namespaces.id(" 100 "), Some(100));
+        assert_eq!(namespaces.id("main"), Some(Namespaces::MAIN));
+        assert_eq!(namespaces.id("kategorie"), Some(Namespaces::CATEGORY));
+        assert_eq!(namespaces.id("Portal"), Some(100));
+        assert_eq!(namespaces.id("118"), None);
+        assert_eq!(namespaces.id("Draft"), None);
+    }
+
+    #[test]
+    fn test_read_revisions() {
+        let history = r#"<mediawiki>
+  <page>
+    <title>Bonn</title>
+    <ns>0</ns>
+    <revision>
+      <timestamp>2001-01-01T00:00:00Z</timestamp>
+      <text bytes="12" xml:space="preserve">[[Germany]] &amp; [[Rhine]]</text>
+    </revision>
+    <revision>
+      <timestamp>2002-01-01T00:00:00Z</timestamp>
+      <text deleted="deleted" />
+    </revision>
+    <revision>
+      <timestamp>2003-01-01T00:00:00Z</timestamp>
+      <text bytes="0" />
+    </revision>
+  </page>
+  <page>
+    <title>Talk:Bonn</title>
+    <ns>1</ns>
+    <revision>
+      <timestamp>2004-01-01T00:00:00Z</timestamp>
+      <text bytes="5">Hello</text>
+    </revision>
+  </page>
+</mediawiki>"#;
+
+        let revisions = WikiRevisions::new(Reader::from_str(history))
+            .map(|revision| (revision.title, revision.namespace_id, revision.timestamp, revision.text))
+            .collect::<Vec<_>>();
+
+        assert_eq!(revisions, vec![
+            ("Bonn".to_string(), 0, "2001-01-01T00:00:00Z".to_string(), Some("[[Germany]] & [[Rhine]]".to_string())),
+            ("Bonn".to_string(), 0, "2002-01-01T00:00:00Z".to_string(), None),
+            ("Bonn".to_string(), 0, "2003-01-01T00:00:00Z".to_string(), Some(String::new())),
+            ("Talk:Bonn".to_string(), 1, "2004-01-01T00:00:00Z".to_string(), Some("Hello".to_string()))
+        ]);
+    }
+}
@@ -0,0 +1,76 @@
+use std::{collections::HashMap, fs::File, io::{BufRead, BufReader, Result, Write}, path::PathBuf};
+
+use serde::{Deserialize, Serialize};
+
+use crate::network_generator::{wiki_text::{is_disambiguation, is_stub, parse_citations_with_templates, Template}, wiki_xml_dump::WikiPage};
+
+/// Attributes of an article which are collected while the network is generated and saved next to
+/// it as JSON lines.
+#[derive(Serialize, Deserialize, Debug, Default, Clone, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct NodeRecord {
+    pub title: String,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub namespace: Option<i16>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub citations: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub disambiguation: Option<bool>,
+    /// Size of the wikitext in bytes.
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub length: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub stub: Option<bool>,
+    /// Whether the article is a list like "List of rivers of Germany".
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub list: Option<bool>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub links: Option<usize>,
+    #[serde(default, skip_serializing_if = "Option::is_none")]
+    pub templates: Option<usize>
+}
+
+/// The attributes of an article with the `links` it has in the network and its `templates`, which
+/// were already parsed for the network.
+pub fn node_record(page: &WikiPage, links: &[String], templates: &[Template]) -> NodeRecord {
+    let text = page.text.as_deref().unwrap_or_default();
+
+    NodeRecord {
+        title: page.title.to_owned(),
+        namespace: Some(page.namespace_id),
+        citations: Some(parse_citations_with_templates(text, templates).len()),
+        disambiguation: Some(is_disambiguation(text, templates)),
+        length: Some(text.len()),
+        stub: Some(is_stub(templates)),
+        list: Some(is_list(&page.title)),
+        links: Some(links.len()),
+        templates: Some(templates.len())
+    }
+}
+
+fn is_list(title: &str) -> bool {
+    title.starts_with("List of ") || title.starts_with("Lists of ")
+}
+
+pub type NodeAttributes = HashMap<String, NodeRecord>;
+
+pub fn load_node_attributes(node_file_path: PathBuf) -> Result<NodeAttributes> {
+    let reader = BufReader::new(File::open(node_file_path)?);
+    let mut nodes = HashMap::new();
+
+    for line in reader.lines() {
+        let line = line?;
+
+        if !line.trim().is_empty() {
+            let node = serde_json::from_str::<NodeRecord>(&line)?;
+            nodes.insert(node.title.to_owned(), node);
+        }
+    }
+
+    Ok(nodes)
+}
+
+pub fn write_node_record<W: Write>(writer: &mut W, node: &NodeRecord) -> Result<()> {
+    serde_json::to_writer(&mut *writer, node)?;
+    writer.write_all(b"\n")
+}
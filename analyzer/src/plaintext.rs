@@ -0,0 +1,56 @@
+use std::{fs::File, io::{BufWriter, Result, Write}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{common::{find_entries, process_dump, read_from, read_page_ids}, network_generator::{wiki_text::{is_redirect, parse_text, plain_text}, wiki_xml_dump::{Namespaces, WikiPage}}};
+
+#[derive(Serialize, Debug)]
+struct PlainTextArticle {
+    id: Option<u64>,
+    title: String,
+    text: String
+}
+
+pub fn plaintext(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String) -> Result<()> {
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                println!("{}", plain_text(&page).unwrap_or("No wikitext!".to_owned()));
+                return Ok(());
+            }
+        }
+    }
+
+    println!("No article with title {title} found!");
+
+    Ok(())
+}
+
+/// Writes one JSON object with id, title and text per line for every article of the dump.
+pub fn all_plaintext(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, output_file_path: PathBuf) -> Result<()> {
+    let number_of_threads = 4;
+    let page_ids = read_page_ids(xml_dump_index_path)?;
+
+    let process = |page: WikiPage| {
+        if page.namespace_id != Namespaces::MAIN || parse_text(&page).is_none_or(|links| is_redirect(&links)) {
+            return None;
+        }
+
+        Some(PlainTextArticle {
+            id: page_ids.get(&page.title).copied(),
+            text: plain_text(&page)?,
+            title: page.title
+        })
+    };
+
+    process_dump(xml_dump_path, xml_dump_index_path, number_of_threads, process, |articles| {
+        let mut writer = BufWriter::new(File::create(output_file_path)?);
+
+        for article in articles {
+            serde_json::to_writer(&mut writer, &article)?;
+            writer.write_all(b"\n")?;
+        }
+
+        writer.flush()
+    })
+}
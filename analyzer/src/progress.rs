@@ -0,0 +1,189 @@
+use std::{io::{Read, Result}, sync::atomic::{AtomicU64, Ordering}, time::{Duration, Instant}};
+
+use serde::Serialize;
+
+/// How often the progress is reported while the dump is read.
+pub const REPORT_INTERVAL: Duration = Duration::from_secs(10);
+
+#[derive(Clone, Copy, PartialEq, Debug, Default)]
+pub enum ProgressFormat {
+    /// Lines for humans on the standard output.
+    #[default]
+    Text,
+    /// One JSON event per line on the standard error, so they do not mix with the other output.
+    Json
+}
+
+impl ProgressFormat {
+    pub const NAMES: [&'static str; 2] = ["text", "json"];
+
+    pub fn from_name(name: &str) -> Option<ProgressFormat> {
+        match name {
+            "text" => Some(ProgressFormat::Text),
+            "json" => Some(ProgressFormat::Json),
+            _ => None
+        }
+    }
+}
+
+#[derive(Serialize, Debug)]
+#[serde(tag = "event", rename_all = "camelCase", rename_all_fields = "camelCase")]
+enum ProgressEvent {
+    Progress {
+        bytes_read: u64,
+        total_bytes: u64,
+        pages: u64,
+        links: u64,
+        pages_per_second: f64,
+        links_per_second: f64,
+        elapsed_seconds: f64,
+        eta_seconds: Option<f64>
+    },
+    PartFinished {
+        part: usize,
+        finished_parts: usize,
+        parts: usize
+    },
+    Finished {
+        pages: u64,
+        links: u64,
+        elapsed_seconds: f64
+    }
+}
+
+/// Counts the compressed bytes, pages and links the decoding threads got through and estimates
+/// the remaining time from the bytes still to read.
+pub struct Progress {
+    format: ProgressFormat,
+    start: Instant,
+    total_bytes: u64,
+    /// Bytes of the parts finished by an earlier, interrupted run.
+    skipped_bytes: u64,
+    bytes_read: AtomicU64,
+    pages: AtomicU64,
+    links: AtomicU64
+}
+
+impl Progress {
+    pub fn new(format: ProgressFormat, total_bytes: u64, skipped_bytes: u64) -> Progress {
+        Progress {
+            format,
+            start: Instant::now(),
+            total_bytes,
+            skipped_bytes,
+            bytes_read: AtomicU64::new(0),
+            pages: AtomicU64::new(0),
+            links: AtomicU64::new(0)
+        }
+    }
+
+    pub fn add_page(&self, number_of_links: usize) {
+        self.pages.fetch_add(1, Ordering::Relaxed);
+        self.links.fetch_add(number_of_links as u64, Ordering::Relaxed);
+    }
+
+    pub fn report(&self) {
+        let elapsed_seconds = self.start.elapsed().as_secs_f64();
+        let bytes_read = self.bytes_read.load(Ordering::Relaxed);
+        let pages = self.pages.load(Ordering::Relaxed);
+        let links = self.links.load(Ordering::Relaxed);
+        let bytes_per_second = bytes_read as f64 / elapsed_seconds;
+        let remaining_bytes = self.total_bytes.saturating_sub(self.skipped_bytes + bytes_read);
+
+        self.emit(ProgressEvent::Progress {
+            bytes_read: self.skipped_bytes + bytes_read,
+            total_bytes: self.total_bytes,
+            pages,
+            links,
+            pages_per_second: pages as f64 / elapsed_seconds,
+            links_per_second: links as f64 / elapsed_seconds,
+            elapsed_seconds,
+            eta_seconds: (bytes_per_second > 0.0).then(|| remaining_bytes as f64 / bytes_per_second)
+        });
+    }
+
+    pub fn part_finished(&self, part: usize, finished_parts: usize, parts: usize) {
+        self.emit(ProgressEvent::PartFinished { part, finished_parts, parts });
+    }
+
+    pub fn finish(&self) {
+        self.emit(ProgressEvent::Finished {
+            pages: self.pages.load(Ordering::Relaxed),
+            links: self.links.load(Ordering::Relaxed),
+            elapsed_seconds: self.start.elapsed().as_secs_f64()
+        });
+    }
+
+    fn emit(&self, event: ProgressEvent) {
+        match self.format {
+            ProgressFormat::Json => eprintln!("{}", serde_json::to_string(&event).unwrap()),
+            ProgressFormat::Text => println!("{}", describe(&event))
+        }
+    }
+}
+
+fn describe(event: &ProgressEvent) -> String {
+    match event {
+        ProgressEvent::Progress { bytes_read, total_bytes, pages_per_second, links_per_second, eta_seconds, .. } => format!(
+            "{:.1} % ({} of {}), {:.0} pages/s, {:.0} links/s, ETA {}",
+            *bytes_read as f64 * 100.0 / (*total_bytes).max(1) as f64,
+            format_bytes(*bytes_read),
+            format_bytes(*total_bytes),
+            pages_per_second,
+            links_per_second,
+            eta_seconds.map(format_duration).unwrap_or("unknown".to_owned())),
+        ProgressEvent::PartFinished { part, finished_parts, parts } => format!("finished part {part} ({finished_parts} of {parts})"),
+        ProgressEvent::Finished { pages, links, elapsed_seconds } => format!("read {pages} pages with {links} links in {}", format_duration(*elapsed_seconds))
+    }
+}
+
+fn format_bytes(bytes: u64) -> String {
+    const UNITS: [&str; 4] = ["B", "KB", "MB", "GB"];
+
+    let mut value = bytes as f64;
+    let mut unit = 0;
+
+    while value >= 1000.0 && unit < UNITS.len() - 1 {
+        value /= 1000.0;
+        unit += 1;
+    }
+
+    format!("{value:.1} {}", UNITS[unit])
+}
+
+fn format_duration(seconds: f64) -> String {
+    let seconds = seconds.round() as u64;
+    format!("{}h {:02}m {:02}s", seconds / 3600, seconds / 60 % 60, seconds % 60)
+}
+
+/// Adds the number of bytes read from the inner reader to the progress.
+pub struct CountingReader<'a, R> {
+    inner: R,
+    progress: &'a Progress
+}
+
+impl<'a, R> CountingReader<'a, R> {
+    pub fn new(inner: R, progress: &'a Progress) -> CountingReader<'a, R> {
+        CountingReader { inner, progress }
+    }
+}
+
+impl<R: Read> Read for CountingReader<'_, R> {
+    fn read(&mut self, buf: &mut [u8]) -> Result<usize> {
+        let bytes_read = self.inner.read(buf)?;
+        self.progress.bytes_read.fetch_add(bytes_read as u64, Ordering::Relaxed);
+        Ok(bytes_read)
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{format_bytes, format_duration};
+
+    #[test]
+    fn test_formatting() {
+        assert_eq!(format_bytes(999), "999.0 B");
+        assert_eq!(format_bytes(22_400_000_000), "22.4 GB");
+        assert_eq!(format_duration(3723.4), "1h 02m 03s");
+    }
+}
@@ -0,0 +1,30 @@
+use std::{io::Result, path::PathBuf};
+
+use crate::{common::{find_entries, read_from}, network_generator::wiki_text::parse_sections};
+
+/// Prints the outline of an article with the number of links in every section.
+pub fn sections(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String) -> Result<()> {
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                let Some(sections) = parse_sections(&page) else {
+                    println!("No wikitext!");
+                    return Ok(());
+                };
+
+                for section in sections {
+                    let indentation = "  ".repeat(section.path.len().saturating_sub(1));
+                    let name = section.path.last().map_or("(lead)", |heading| heading.as_str());
+
+                    println!("{indentation}{name}\t{} links", section.links.len());
+                }
+
+                return Ok(());
+            }
+        }
+    }
+
+    println!("No article with title {title} found!");
+
+    Ok(())
+}
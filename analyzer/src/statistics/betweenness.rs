@@ -0,0 +1,100 @@
+use std::collections::VecDeque;
+
+use rand::{rngs::StdRng, seq::index::sample, SeedableRng};
+
+use super::indexed_network::IndexedNetwork;
+
+/// Approximates the betweenness centrality of every node with Brandes' algorithm, run only from
+/// `number_of_samples` randomly drawn source nodes. The partial sums are scaled up to the size of
+/// the network. With at least as many samples as nodes the result is exact.
+pub fn approximate_betweenness(network: &IndexedNetwork, number_of_samples: usize, seed: u64) -> Vec<f64> {
+    let number_of_nodes = network.len();
+    let mut betweenness = vec![0.0; number_of_nodes];
+
+    if number_of_nodes == 0 || number_of_samples == 0 {
+        return betweenness;
+    }
+
+    let sources = if number_of_samples >= number_of_nodes {
+        (0..number_of_nodes).collect::<Vec<usize>>()
+    } else {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let mut sources = sample(&mut rng, number_of_nodes, number_of_samples).into_vec();
+        sources.sort_unstable();
+        sources
+    };
+
+    let mut distances = vec![usize::MAX; number_of_nodes];
+    let mut shortest_paths = vec![0.0; number_of_nodes];
+    let mut dependencies = vec![0.0; number_of_nodes];
+    let mut visited = Vec::with_capacity(number_of_nodes);
+    let mut queue = VecDeque::new();
+
+    for source in &sources {
+        distances[*source] = 0;
+        shortest_paths[*source] = 1.0;
+        queue.push_back(*source);
+
+        while let Some(node) = queue.pop_front() {
+            visited.push(node);
+
+            for target in &network.out_edges[node] {
+                if distances[*target] == usize::MAX {
+                    distances[*target] = distances[node] + 1;
+                    queue.push_back(*target);
+                }
+
+                if distances[*target] == distances[node] + 1 {
+                    shortest_paths[*target] += shortest_paths[node];
+                }
+            }
+        }
+
+        // nodes are visited in order of decreasing distance, so the dependencies of all
+        // successors on shortest paths are complete when a node is reached
+        for node in visited.iter().rev() {
+            for target in &network.out_edges[*node] {
+                if distances[*target] == distances[*node] + 1 {
+                    dependencies[*node] += shortest_paths[*node] / shortest_paths[*target] * (1.0 + dependencies[*target]);
+                }
+            }
+
+            if node != source {
+                betweenness[*node] += dependencies[*node];
+            }
+        }
+
+        for node in visited.drain(..) {
+            distances[node] = usize::MAX;
+            shortest_paths[node] = 0.0;
+            dependencies[node] = 0.0;
+        }
+    }
+
+    let scale = number_of_nodes as f64 / sources.len() as f64;
+    betweenness.iter_mut().for_each(|value| *value *= scale);
+
+    betweenness
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::statistics::indexed_network::IndexedNetwork;
+
+    use super::approximate_betweenness;
+
+    #[test]
+    fn test_exact_betweenness_of_path() {
+        let network = HashMap::from([
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec!["C".to_string()]),
+            ("C".to_string(), vec!["D".to_string()]),
+            ("D".to_string(), vec![])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        assert_eq!(approximate_betweenness(&indexed_network, 4, 0), vec![0.0, 2.0, 2.0, 0.0]);
+    }
+}
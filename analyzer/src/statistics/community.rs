@@ -0,0 +1,301 @@
+use rand::{rngs::StdRng, seq::SliceRandom, SeedableRng};
+
+use super::indexed_network::IndexedNetwork;
+
+/// Undirected weighted graph used by the Louvain method. Every edge between two different
+/// nodes is stored in the adjacency lists of both nodes, self loops are kept separately.
+struct WeightedGraph {
+    adjacency: Vec<Vec<(usize, f64)>>,
+    self_loops: Vec<f64>
+}
+
+impl WeightedGraph {
+    fn from_network(network: &IndexedNetwork) -> WeightedGraph {
+        let mut neighbours = vec![Vec::new(); network.len()];
+        let mut self_loops = vec![0.0; network.len()];
+
+        for (source, targets) in network.out_edges.iter().enumerate() {
+            for target in targets {
+                if source == *target {
+                    self_loops[source] += 1.0;
+                } else {
+                    neighbours[source].push(*target);
+                    neighbours[*target].push(source);
+                }
+            }
+        }
+
+        let adjacency = neighbours.into_iter()
+            .map(|mut nodes| {
+                nodes.sort_unstable();
+                let mut weighted: Vec<(usize, f64)> = Vec::with_capacity(nodes.len());
+
+                for node in nodes {
+                    match weighted.last_mut() {
+                        Some((last, weight)) if *last == node => *weight += 1.0,
+                        _ => weighted.push((node, 1.0))
+                    }
+                }
+
+                weighted
+            })
+            .collect();
+
+        WeightedGraph { adjacency, self_loops }
+    }
+
+    fn len(&self) -> usize {
+        self.adjacency.len()
+    }
+
+    fn degree(&self, node: usize) -> f64 {
+        self.adjacency[node].iter().map(|(_, weight)| weight).sum::<f64>() + 2.0 * self.self_loops[node]
+    }
+
+    fn total_degree(&self) -> f64 {
+        (0..self.len()).map(|node| self.degree(node)).sum()
+    }
+
+    /// Collapses every community into a single node.
+    fn aggregate(&self, communities: &[usize], number_of_communities: usize) -> WeightedGraph {
+        let mut self_loops = vec![0.0; number_of_communities];
+        let mut neighbours: Vec<Vec<(usize, f64)>> = vec![Vec::new(); number_of_communities];
+
+        for node in 0..self.len() {
+            let community = communities[node];
+            self_loops[community] += self.self_loops[node];
+
+            for (neighbour, weight) in &self.adjacency[node] {
+                let neighbour_community = communities[*neighbour];
+
+                if neighbour_community == community {
+                    // every internal edge is visited once from each end
+                    self_loops[community] += weight / 2.0;
+                } else {
+                    neighbours[community].push((neighbour_community, *weight));
+                }
+            }
+        }
+
+        let adjacency = neighbours.into_iter()
+            .map(|mut edges| {
+                edges.sort_unstable_by_key(|(node, _)| *node);
+                let mut merged: Vec<(usize, f64)> = Vec::with_capacity(edges.len());
+
+                for (node, weight) in edges {
+                    match merged.last_mut() {
+                        Some((last, total)) if *last == node => *total += weight,
+                        _ => merged.push((node, weight))
+                    }
+                }
+
+                merged
+            })
+            .collect();
+
+        WeightedGraph { adjacency, self_loops }
+    }
+}
+
+pub struct Partition {
+    pub communities: Vec<usize>,
+    pub number_of_communities: usize,
+    pub modularity: f64
+}
+
+/// Partitions the network into communities with the Louvain method on the undirected version
+/// of the network. Nodes are visited in an order drawn from `seed`, so equal seeds give equal
+/// partitions.
+pub fn louvain(network: &IndexedNetwork, seed: u64, resolution: f64) -> Partition {
+    let mut rng = StdRng::seed_from_u64(seed);
+    let original = WeightedGraph::from_network(network);
+    let mut graph = WeightedGraph::from_network(network);
+    let mut communities = (0..network.len()).collect::<Vec<usize>>();
+
+    loop {
+        let (level_communities, number_of_level_communities, improved) = move_nodes(&graph, &mut rng, resolution);
+
+        if !improved {
+            break;
+        }
+
+        for community in communities.iter_mut() {
+            *community = level_communities[*community];
+        }
+
+        graph = graph.aggregate(&level_communities, number_of_level_communities);
+    }
+
+    let (communities, number_of_communities) = renumber(&communities);
+    let modularity = modularity(&original, &communities, number_of_communities, resolution);
+
+    Partition { communities, number_of_communities, modularity }
+}
+
+/// One level of the Louvain method: nodes are moved to the neighbouring community with the
+/// largest modularity gain until no move improves the partition.
+fn move_nodes(graph: &WeightedGraph, rng: &mut StdRng, resolution: f64) -> (Vec<usize>, usize, bool) {
+    let number_of_nodes = graph.len();
+    let total_degree = graph.total_degree();
+    let mut communities = (0..number_of_nodes).collect::<Vec<usize>>();
+
+    if total_degree == 0.0 {
+        return (communities, number_of_nodes, false);
+    }
+
+    let degrees = (0..number_of_nodes).map(|node| graph.degree(node)).collect::<Vec<f64>>();
+    let mut community_degrees = degrees.clone();
+
+    let mut order = (0..number_of_nodes).collect::<Vec<usize>>();
+    order.shuffle(rng);
+
+    let mut neighbour_weights = vec![0.0; number_of_nodes];
+    let mut neighbour_communities = Vec::new();
+    let mut improved = false;
+    let mut moved = true;
+
+    while moved {
+        moved = false;
+
+        for node in &order {
+            let node = *node;
+            let current_community = communities[node];
+
+            for (neighbour, weight) in &graph.adjacency[node] {
+                let community = communities[*neighbour];
+
+                if neighbour_weights[community] == 0.0 {
+                    neighbour_communities.push(community);
+                }
+
+                neighbour_weights[community] += weight;
+            }
+
+            community_degrees[current_community] -= degrees[node];
+
+            let gain = |community: usize, weight: f64| {
+                weight - resolution * community_degrees[community] * degrees[node] / total_degree
+            };
+
+            let mut best_community = current_community;
+            let mut best_gain = gain(current_community, neighbour_weights[current_community]);
+
+            for community in &neighbour_communities {
+                let community_gain = gain(*community, neighbour_weights[*community]);
+
+                if community_gain > best_gain {
+                    best_gain = community_gain;
+                    best_community = *community;
+                }
+            }
+
+            community_degrees[best_community] += degrees[node];
+
+            if best_community != current_community {
+                communities[node] = best_community;
+                moved = true;
+                improved = true;
+            }
+
+            for community in neighbour_communities.drain(..) {
+                neighbour_weights[community] = 0.0;
+            }
+        }
+    }
+
+    let (communities, number_of_communities) = renumber(&communities);
+
+    (communities, number_of_communities, improved)
+}
+
+/// Maps community labels to consecutive ids in order of first appearance.
+fn renumber(communities: &[usize]) -> (Vec<usize>, usize) {
+    let mut new_ids = vec![usize::MAX; communities.len()];
+    let mut next_id = 0;
+
+    let renumbered = communities.iter()
+        .map(|community| {
+            if new_ids[*community] == usize::MAX {
+                new_ids[*community] = next_id;
+                next_id += 1;
+            }
+
+            new_ids[*community]
+        })
+        .collect();
+
+    (renumbered, next_id)
+}
+
+fn modularity(graph: &WeightedGraph, communities: &[usize], number_of_communities: usize, resolution: f64) -> f64 {
+    let total_degree = graph.total_degree();
+
+    if total_degree == 0.0 {
+        return 0.0;
+    }
+
+    let mut internal_weights = vec![0.0; number_of_communities];
+    let mut community_degrees = vec![0.0; number_of_communities];
+
+    for node in 0..graph.len() {
+        let community = communities[node];
+        community_degrees[community] += graph.degree(node);
+        internal_weights[community] += 2.0 * graph.self_loops[node];
+
+        for (neighbour, weight) in &graph.adjacency[node] {
+            if communities[*neighbour] == community {
+                internal_weights[community] += weight;
+            }
+        }
+    }
+
+    internal_weights.iter()
+        .zip(community_degrees.iter())
+        .map(|(internal, degree)| internal / total_degree - resolution * (degree / total_degree).powi(2))
+        .sum()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::statistics::indexed_network::IndexedNetwork;
+
+    use super::louvain;
+
+    fn network(edges: &[(&str, &str)]) -> HashMap<String, Vec<String>> {
+        let mut network: HashMap<String, Vec<String>> = HashMap::new();
+
+        for (source, target) in edges {
+            network.entry(source.to_string()).or_default().push(target.to_string());
+            network.entry(target.to_string()).or_default();
+        }
+
+        network
+    }
+
+    #[test]
+    fn test_louvain_separates_two_triangles() {
+        let network = network(&[("A", "B"), ("B", "C"), ("C", "A"), ("D", "E"), ("E", "F"), ("F", "D"), ("C", "D")]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        let partition = louvain(&indexed_network, 42, 1.0);
+
+        assert_eq!(partition.number_of_communities, 2);
+        assert_eq!(partition.communities[0], partition.communities[1]);
+        assert_eq!(partition.communities[1], partition.communities[2]);
+        assert_ne!(partition.communities[2], partition.communities[3]);
+        assert!((partition.modularity - 5.0 / 14.0).abs() < 1e-9);
+    }
+
+    #[test]
+    fn test_louvain_is_reproducible() {
+        let network = network(&[("A", "B"), ("B", "C"), ("C", "D"), ("D", "A"), ("A", "C"), ("E", "F"), ("F", "G"), ("G", "E"), ("D", "E")]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        let first = louvain(&indexed_network, 7, 1.0);
+        let second = louvain(&indexed_network, 7, 1.0);
+
+        assert_eq!(first.communities, second.communities);
+    }
+}
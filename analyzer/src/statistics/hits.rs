@@ -0,0 +1,85 @@
+use super::indexed_network::IndexedNetwork;
+
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-10;
+
+/// Computes hub and authority scores with Kleinberg's HITS algorithm. Both score vectors are
+/// normalised to sum up to one.
+pub fn hits(network: &IndexedNetwork) -> (Vec<f64>, Vec<f64>) {
+    if network.is_empty() {
+        return (Vec::new(), Vec::new());
+    }
+
+    let number_of_nodes = network.len();
+    let mut hubs = vec![1.0 / number_of_nodes as f64; number_of_nodes];
+    let mut authorities = vec![0.0; number_of_nodes];
+
+    for _ in 0..MAX_ITERATIONS {
+        authorities.iter_mut().for_each(|authority| *authority = 0.0);
+
+        for (node, targets) in network.out_edges.iter().enumerate() {
+            for target in targets {
+                authorities[*target] += hubs[node];
+            }
+        }
+
+        normalise(&mut authorities);
+
+        let mut new_hubs = network.out_edges.iter()
+            .map(|targets| targets.iter().map(|target| authorities[*target]).sum())
+            .collect::<Vec<f64>>();
+
+        normalise(&mut new_hubs);
+
+        let change: f64 = hubs.iter()
+            .zip(new_hubs.iter())
+            .map(|(old, new)| (old - new).abs())
+            .sum();
+
+        hubs = new_hubs;
+
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    (hubs, authorities)
+}
+
+fn normalise(scores: &mut [f64]) {
+    let total: f64 = scores.iter().sum();
+
+    if total > 0.0 {
+        scores.iter_mut().for_each(|score| *score /= total);
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::statistics::indexed_network::IndexedNetwork;
+
+    use super::hits;
+
+    #[test]
+    fn test_hits_of_bipartite_network() {
+        let network = HashMap::from([
+            ("A".to_string(), vec!["C".to_string(), "D".to_string()]),
+            ("B".to_string(), vec!["C".to_string()]),
+            ("C".to_string(), vec![]),
+            ("D".to_string(), vec![])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        let (hubs, authorities) = hits(&indexed_network);
+        // The principal eigenvectors split the scores in the golden ratio.
+        let golden_ratio = (1.0 + 5.0_f64.sqrt()) / 2.0;
+        let expected_hubs = [1.0 / golden_ratio, 1.0 / golden_ratio.powi(2), 0.0, 0.0];
+        let expected_authorities = [0.0, 0.0, 1.0 / golden_ratio, 1.0 / golden_ratio.powi(2)];
+
+        for (score, expected) in hubs.iter().chain(authorities.iter()).zip(expected_hubs.iter().chain(expected_authorities.iter())) {
+            assert!((score - expected).abs() < 1e-6, "{score} != {expected}");
+        }
+    }
+}
@@ -0,0 +1,42 @@
+use std::collections::HashMap;
+
+/// A compact view of a network where every article is identified by its position in `nodes`.
+/// Nodes are sorted by title so that algorithms iterating over them behave the same on every run.
+pub struct IndexedNetwork<'a> {
+    pub nodes: Vec<&'a String>,
+    pub out_edges: Vec<Vec<usize>>
+}
+
+impl<'a> IndexedNetwork<'a> {
+    pub fn new(network: &'a HashMap<String, Vec<String>>) -> IndexedNetwork<'a> {
+        let mut nodes = network.keys().collect::<Vec<&String>>();
+        nodes.sort();
+
+        let index = nodes.iter()
+            .enumerate()
+            .map(|(position, node)| (*node, position))
+            .collect::<HashMap<&String, usize>>();
+
+        let mut out_edges = Vec::with_capacity(nodes.len());
+
+        for node in nodes.iter() {
+            let mut targets = network[*node].iter()
+                .filter_map(|link| index.get(link).copied())
+                .collect::<Vec<usize>>();
+            targets.sort_unstable();
+            targets.dedup();
+
+            out_edges.push(targets);
+        }
+
+        IndexedNetwork { nodes, out_edges }
+    }
+
+    pub fn len(&self) -> usize {
+        self.nodes.len()
+    }
+
+    pub fn is_empty(&self) -> bool {
+        self.nodes.is_empty()
+    }
+}
@@ -0,0 +1,93 @@
+use super::indexed_network::IndexedNetwork;
+
+/// Computes the core number of every node with the bucket algorithm of Batagelj and Zaversnik.
+/// Link directions are ignored, so the degree of a node is its number of distinct neighbours.
+pub fn core_numbers(network: &IndexedNetwork) -> Vec<usize> {
+    let number_of_nodes = network.len();
+    let mut neighbours = vec![Vec::new(); number_of_nodes];
+
+    for (node, targets) in network.out_edges.iter().enumerate() {
+        for target in targets {
+            if *target != node {
+                neighbours[node].push(*target);
+                neighbours[*target].push(node);
+            }
+        }
+    }
+
+    for node_neighbours in neighbours.iter_mut() {
+        node_neighbours.sort_unstable();
+        node_neighbours.dedup();
+    }
+
+    let mut degrees = neighbours.iter().map(|node_neighbours| node_neighbours.len()).collect::<Vec<usize>>();
+    let max_degree = degrees.iter().copied().max().unwrap_or(0);
+
+    // nodes sorted by degree, bucket_starts[d] is the position of the first node of degree d
+    let mut bucket_starts = vec![0; max_degree + 2];
+    for degree in &degrees {
+        bucket_starts[*degree + 1] += 1;
+    }
+    for degree in 1..bucket_starts.len() {
+        bucket_starts[degree] += bucket_starts[degree - 1];
+    }
+
+    let mut sorted_nodes = vec![0; number_of_nodes];
+    let mut positions = vec![0; number_of_nodes];
+    let mut next_positions = bucket_starts.clone();
+    for node in 0..number_of_nodes {
+        positions[node] = next_positions[degrees[node]];
+        sorted_nodes[positions[node]] = node;
+        next_positions[degrees[node]] += 1;
+    }
+
+    for index in 0..number_of_nodes {
+        let node = sorted_nodes[index];
+
+        for neighbour in &neighbours[node] {
+            let neighbour = *neighbour;
+
+            if degrees[neighbour] > degrees[node] {
+                // move the neighbour to the front of its bucket and shrink the bucket
+                let neighbour_degree = degrees[neighbour];
+                let first_position = bucket_starts[neighbour_degree];
+                let first_node = sorted_nodes[first_position];
+
+                if first_node != neighbour {
+                    sorted_nodes.swap(first_position, positions[neighbour]);
+                    positions[first_node] = positions[neighbour];
+                    positions[neighbour] = first_position;
+                }
+
+                bucket_starts[neighbour_degree] += 1;
+                degrees[neighbour] -= 1;
+            }
+        }
+    }
+
+    degrees
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::statistics::indexed_network::IndexedNetwork;
+
+    use super::core_numbers;
+
+    #[test]
+    fn test_core_numbers_of_clique_with_tail() {
+        let network = HashMap::from([
+            ("A".to_string(), vec!["B".to_string(), "C".to_string(), "D".to_string()]),
+            ("B".to_string(), vec!["C".to_string(), "D".to_string()]),
+            ("C".to_string(), vec!["D".to_string(), "A".to_string()]),
+            ("D".to_string(), vec!["E".to_string()]),
+            ("E".to_string(), vec!["F".to_string()]),
+            ("F".to_string(), vec![])
+        ]);
+        let indexed_network = IndexedNetwork::new(&network);
+
+        assert_eq!(core_numbers(&indexed_network), vec![3, 3, 3, 3, 1, 1]);
+    }
+}
@@ -0,0 +1,499 @@
+use std::collections::{BTreeMap, HashMap};
+
+use serde::Serialize;
+use serde_json::{json, Value};
+
+use crate::node_attributes::NodeRecord;
+
+use super::{betweenness::approximate_betweenness, correlation::rank_correlation, hits::hits, indexed_network::IndexedNetwork, k_core::core_numbers, page_rank::{page_rank, DAMPING_FACTOR}, power_law::{fit_power_law, PowerLawFit}, top_k::{top_k, ScoredNode}, summary::{calculate_in_degrees, calculate_out_degrees, complementary_cumulative_distribution, degree_histogram, find_max_degree, logarithmic_bins, number_of_edges, number_of_nodes}, AnalyzedNetwork, Metric};
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SummaryStatistics {
+    number_of_nodes: usize,
+    number_of_edges: usize
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DegreeStatistics {
+    nodes_of_max_out_degree: Vec<String>,
+    max_out_degree: usize,
+    nodes_of_max_in_degree: Vec<String>,
+    max_in_degree: usize,
+    top_out_degree: Vec<ScoredNode<usize>>,
+    top_in_degree: Vec<ScoredNode<usize>>,
+    out_degree_distribution: Vec<(usize, usize)>,
+    in_degree_distribution: Vec<(usize, usize)>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    out_degree_ccdf: Option<Vec<(usize, f64)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_degree_ccdf: Option<Vec<(usize, f64)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    out_degree_log_bins: Option<Vec<(f64, f64)>>,
+    #[serde(skip_serializing_if = "Option::is_none")]
+    in_degree_log_bins: Option<Vec<(f64, f64)>>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct HitsStatistics {
+    hubs: Vec<ScoredNode<f64>>,
+    authorities: Vec<ScoredNode<f64>>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct KCoreStatistics {
+    max_core: usize,
+    nodes_of_max_core: Vec<String>,
+    top_core_number: Vec<ScoredNode<usize>>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerLawStatistics {
+    out_degree: Option<PowerLawFit>,
+    in_degree: Option<PowerLawFit>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct CitationStatistics {
+    number_of_articles: usize,
+    number_of_citations: usize,
+    mean_citations: f64,
+    median_citations: usize,
+    number_of_articles_without_citations: usize,
+    citation_distribution: Vec<(usize, usize)>,
+    top_citations: Vec<ScoredNode<usize>>,
+    /// The most linked articles without any citation.
+    uncited_by_in_degree: Vec<ScoredNode<usize>>,
+    /// Spearman's rank correlation between the number of citations and the in-degree.
+    in_degree_correlation: Option<f64>
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct DisambiguationStatistics {
+    /// Counted from the node records, so the pages left out of networks generated with
+    /// `--disambiguation exclude` or `collapse` count as well.
+    number_of_disambiguation_pages: usize,
+    /// Links from articles which are no disambiguation pages themselves. Only networks generated
+    /// with `--disambiguation keep` still have such links.
+    number_of_links_to_disambiguation_pages: usize,
+    number_of_articles_linking_to_disambiguation_pages: usize,
+    most_linked_disambiguation_pages: Vec<ScoredNode<usize>>,
+    articles_with_most_disambiguation_links: Vec<ScoredNode<usize>>
+}
+
+/// Degrees of one class of articles like stubs or lists.
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct SegmentStatistics {
+    number_of_articles: usize,
+    mean_out_degree: f64,
+    median_out_degree: usize,
+    mean_in_degree: f64,
+    median_in_degree: usize,
+    mean_length: f64,
+    /// Links per 1000 bytes of wikitext.
+    link_density: f64,
+    mean_templates: f64
+}
+
+pub struct SummaryMetric;
+
+impl Metric for SummaryMetric {
+    fn name(&self) -> &'static str {
+        "summary"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        json!(SummaryStatistics {
+            number_of_nodes: number_of_nodes(network.network),
+            number_of_edges: number_of_edges(network.network)
+        })
+    }
+}
+
+pub struct DegreesMetric {
+    pub ccdf: bool,
+    pub log_bins_per_decade: Option<usize>,
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for DegreesMetric {
+    fn name(&self) -> &'static str {
+        "degrees"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let out_degrees = calculate_out_degrees(network.network);
+        let in_degrees = calculate_in_degrees(network.network);
+        let (nodes_of_max_out_degree, max_out_degree) = find_max_degree(&out_degrees);
+        let (nodes_of_max_in_degree, max_in_degree) = find_max_degree(&in_degrees);
+        let out_degree_distribution = degree_histogram(&out_degrees);
+        let in_degree_distribution = degree_histogram(&in_degrees);
+
+        let ccdf = |histogram: &[(usize, usize)]| self.ccdf.then(|| complementary_cumulative_distribution(histogram));
+        let log_bins = |histogram: &[(usize, usize)]| self.log_bins_per_decade
+            .map(|bins_per_decade| logarithmic_bins(histogram, bins_per_decade));
+
+        json!(DegreeStatistics {
+            nodes_of_max_out_degree,
+            max_out_degree,
+            nodes_of_max_in_degree,
+            max_in_degree,
+            top_out_degree: top_degrees(&out_degrees, self.number_of_top_nodes),
+            top_in_degree: top_degrees(&in_degrees, self.number_of_top_nodes),
+            out_degree_ccdf: ccdf(&out_degree_distribution),
+            in_degree_ccdf: ccdf(&in_degree_distribution),
+            out_degree_log_bins: log_bins(&out_degree_distribution),
+            in_degree_log_bins: log_bins(&in_degree_distribution),
+            out_degree_distribution,
+            in_degree_distribution
+        })
+    }
+}
+
+pub struct PageRankMetric {
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for PageRankMetric {
+    fn name(&self) -> &'static str {
+        "page-rank"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let indexed_network = network.indexed();
+        let ranks = page_rank(indexed_network, DAMPING_FACTOR);
+
+        json!(top_nodes(indexed_network, &ranks, self.number_of_top_nodes))
+    }
+}
+
+pub struct HitsMetric {
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for HitsMetric {
+    fn name(&self) -> &'static str {
+        "hits"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let indexed_network = network.indexed();
+        let (hubs, authorities) = hits(indexed_network);
+
+        json!(HitsStatistics {
+            hubs: top_nodes(indexed_network, &hubs, self.number_of_top_nodes),
+            authorities: top_nodes(indexed_network, &authorities, self.number_of_top_nodes)
+        })
+    }
+}
+
+pub struct BetweennessMetric {
+    pub number_of_samples: usize,
+    pub seed: u64,
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for BetweennessMetric {
+    fn name(&self) -> &'static str {
+        "betweenness"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let indexed_network = network.indexed();
+        let betweenness = approximate_betweenness(indexed_network, self.number_of_samples, self.seed);
+
+        json!(top_nodes(indexed_network, &betweenness, self.number_of_top_nodes))
+    }
+}
+
+pub struct KCoreMetric {
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for KCoreMetric {
+    fn name(&self) -> &'static str {
+        "k-core"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let indexed_network = network.indexed();
+        let core_numbers = core_numbers(indexed_network);
+        let max_core = core_numbers.iter().copied().max().unwrap_or(0);
+
+        json!(KCoreStatistics {
+            max_core,
+            nodes_of_max_core: core_numbers.iter()
+                .enumerate()
+                .filter(|(_, core)| **core == max_core)
+                .map(|(node, _)| indexed_network.nodes[node].to_owned())
+                .collect(),
+            top_core_number: top_k(indexed_network.nodes.iter()
+                .map(|node| node.as_str())
+                .zip(core_numbers.iter().copied()), self.number_of_top_nodes)
+        })
+    }
+}
+
+pub struct PowerLawMetric {
+    pub number_of_bootstraps: usize,
+    pub seed: u64
+}
+
+impl Metric for PowerLawMetric {
+    fn name(&self) -> &'static str {
+        "power-law"
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let fit = |degrees: HashMap<&String, usize>| {
+            fit_power_law(&degree_histogram(&degrees), self.number_of_bootstraps, self.seed)
+        };
+
+        json!(PowerLawStatistics {
+            out_degree: fit(calculate_out_degrees(network.network)),
+            in_degree: fit(calculate_in_degrees(network.network))
+        })
+    }
+}
+
+pub struct CitationsMetric {
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for CitationsMetric {
+    fn name(&self) -> &'static str {
+        "citations"
+    }
+
+    fn needs_node_attributes(&self) -> bool {
+        true
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let Some(nodes) = network.nodes else {
+            return Value::Null;
+        };
+
+        let citations = network.network.keys()
+            .filter_map(|title| Some((title, nodes.get(title)?.citations?)))
+            .collect::<HashMap<&String, usize>>();
+        let in_degrees = calculate_in_degrees(network.network);
+        let in_degree = |title: &String| in_degrees.get(title).copied().unwrap_or(0);
+
+        let mut counts = citations.values().copied().collect::<Vec<usize>>();
+        counts.sort_unstable();
+        let number_of_citations = counts.iter().sum::<usize>();
+
+        let (citation_ranks, in_degree_ranks): (Vec<f64>, Vec<f64>) = citations.iter()
+            .map(|(title, count)| (*count as f64, in_degree(title) as f64))
+            .unzip();
+
+        json!(CitationStatistics {
+            number_of_articles: counts.len(),
+            number_of_citations,
+            mean_citations: number_of_citations as f64 / counts.len().max(1) as f64,
+            median_citations: counts.get(counts.len() / 2).copied().unwrap_or(0),
+            number_of_articles_without_citations: counts.iter().filter(|count| **count == 0).count(),
+            citation_distribution: degree_histogram(&citations),
+            top_citations: top_degrees(&citations, self.number_of_top_nodes),
+            uncited_by_in_degree: top_k(citations.iter()
+                .filter(|(_, count)| **count == 0)
+                .map(|(title, _)| (title.as_str(), in_degree(title))), self.number_of_top_nodes),
+            in_degree_correlation: rank_correlation(&citation_ranks, &in_degree_ranks)
+        })
+    }
+}
+
+pub struct DisambiguationMetric {
+    pub number_of_top_nodes: usize
+}
+
+impl Metric for DisambiguationMetric {
+    fn name(&self) -> &'static str {
+        "disambiguation"
+    }
+
+    fn needs_node_attributes(&self) -> bool {
+        true
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let Some(nodes) = network.nodes else {
+            return Value::Null;
+        };
+
+        let is_disambiguation = |title: &String| nodes.get(title)
+            .and_then(|node| node.disambiguation)
+            .unwrap_or(false);
+
+        let mut links_to_page = HashMap::new();
+        let mut links_of_article = HashMap::new();
+
+        for (title, links) in network.network.iter().filter(|(title, _)| !is_disambiguation(title)) {
+            for link in links.iter().filter(|link| is_disambiguation(link)) {
+                *links_to_page.entry(link).or_insert(0) += 1;
+                *links_of_article.entry(title).or_insert(0) += 1;
+            }
+        }
+
+        json!(DisambiguationStatistics {
+            number_of_disambiguation_pages: nodes.values().filter(|node| node.disambiguation == Some(true)).count(),
+            number_of_links_to_disambiguation_pages: links_to_page.values().sum(),
+            number_of_articles_linking_to_disambiguation_pages: links_of_article.len(),
+            most_linked_disambiguation_pages: top_degrees(&links_to_page, self.number_of_top_nodes),
+            articles_with_most_disambiguation_links: top_degrees(&links_of_article, self.number_of_top_nodes)
+        })
+    }
+}
+
+pub struct SegmentsMetric;
+
+impl Metric for SegmentsMetric {
+    fn name(&self) -> &'static str {
+        "segments"
+    }
+
+    fn needs_node_attributes(&self) -> bool {
+        true
+    }
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value {
+        let Some(nodes) = network.nodes else {
+            return Value::Null;
+        };
+
+        let out_degrees = calculate_out_degrees(network.network);
+        let in_degrees = calculate_in_degrees(network.network);
+        let mut segments: BTreeMap<&str, Vec<&NodeRecord>> = BTreeMap::new();
+
+        for title in network.network.keys() {
+            let Some(node) = nodes.get(title) else {
+                continue;
+            };
+
+            let segment = if node.disambiguation == Some(true) {
+                "disambiguationPages"
+            } else if node.list == Some(true) {
+                "lists"
+            } else if node.stub == Some(true) {
+                "stubs"
+            } else {
+                "articles"
+            };
+
+            segments.entry(segment).or_default().push(node);
+        }
+
+        json!(segments.into_iter()
+            .map(|(segment, nodes)| (segment, segment_statistics(&nodes, &out_degrees, &in_degrees)))
+            .collect::<BTreeMap<&str, SegmentStatistics>>())
+    }
+}
+
+fn segment_statistics(nodes: &[&NodeRecord], out_degrees: &HashMap<&String, usize>, in_degrees: &HashMap<&String, usize>) -> SegmentStatistics {
+    let degrees = |degrees: &HashMap<&String, usize>| {
+        let mut degrees = nodes.iter()
+            .map(|node| degrees.get(&node.title).copied().unwrap_or(0))
+            .collect::<Vec<usize>>();
+        degrees.sort_unstable();
+        degrees
+    };
+    let mean = |sum: usize| sum as f64 / nodes.len().max(1) as f64;
+    let median = |values: &[usize]| values.get(values.len() / 2).copied().unwrap_or(0);
+
+    let out_degrees = degrees(out_degrees);
+    let in_degrees = degrees(in_degrees);
+    let length = nodes.iter().filter_map(|node| node.length).sum::<usize>();
+    let number_of_links = out_degrees.iter().sum::<usize>();
+
+    SegmentStatistics {
+        number_of_articles: nodes.len(),
+        mean_out_degree: mean(number_of_links),
+        median_out_degree: median(&out_degrees),
+        mean_in_degree: mean(in_degrees.iter().sum()),
+        median_in_degree: median(&in_degrees),
+        mean_length: mean(length),
+        link_density: number_of_links as f64 * 1000.0 / length.max(1) as f64,
+        mean_templates: mean(nodes.iter().filter_map(|node| node.templates).sum())
+    }
+}
+
+fn top_nodes(network: &IndexedNetwork, scores: &[f64], number_of_nodes: usize) -> Vec<ScoredNode<f64>> {
+    top_k(network.nodes.iter()
+        .map(|node| node.as_str())
+        .zip(scores.iter().copied()), number_of_nodes)
+}
+
+fn top_degrees(degrees: &HashMap<&String, usize>, number_of_nodes: usize) -> Vec<ScoredNode<usize>> {
+    top_k(degrees.iter().map(|(node, degree)| (node.as_str(), *degree)), number_of_nodes)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::{node_attributes::{NodeAttributes, NodeRecord}, statistics::{AnalyzedNetwork, Metric}};
+
+    use super::{DisambiguationMetric, SegmentsMetric};
+
+    fn network(lines: &[&str]) -> HashMap<String, Vec<String>> {
+        lines.iter()
+            .map(|line| {
+                let mut entries = line.split("; ").map(|entry| entry.to_owned());
+                (entries.next().unwrap(), entries.collect())
+            })
+            .collect()
+    }
+
+    fn nodes(records: Vec<NodeRecord>) -> NodeAttributes {
+        records.into_iter().map(|node| (node.title.to_owned(), node)).collect()
+    }
+
+    #[test]
+    fn test_disambiguation_pages_missing_from_network() {
+        let network = network(&["Berlin; Germany", "Germany; Berlin"]);
+        let nodes = nodes(vec![
+            NodeRecord { title: "Berlin".to_string(), disambiguation: Some(false), ..NodeRecord::default() },
+            NodeRecord { title: "Germany".to_string(), disambiguation: Some(false), ..NodeRecord::default() },
+            NodeRecord { title: "Mercury".to_string(), disambiguation: Some(true), ..NodeRecord::default() }
+        ]);
+
+        let statistics = DisambiguationMetric { number_of_top_nodes: 10 }.compute(&AnalyzedNetwork::new(&network, Some(&nodes)));
+
+        assert_eq!(statistics["numberOfDisambiguationPages"], 1);
+        assert_eq!(statistics["numberOfLinksToDisambiguationPages"], 0);
+    }
+
+    #[test]
+    fn test_segments() {
+        let network = network(&["Berlin; Germany; Spandau", "Germany; Berlin", "Spandau; Berlin", "List of cities; Berlin; Spandau; Germany", "Mercury"]);
+        let nodes = nodes(vec![
+            NodeRecord { title: "Berlin".to_string(), length: Some(2000), templates: Some(4), ..NodeRecord::default() },
+            NodeRecord { title: "Germany".to_string(), length: Some(3000), templates: Some(2), ..NodeRecord::default() },
+            NodeRecord { title: "Spandau".to_string(), length: Some(500), stub: Some(true), templates: Some(1), ..NodeRecord::default() },
+            NodeRecord { title: "List of cities".to_string(), length: Some(1000), list: Some(true), stub: Some(true), ..NodeRecord::default() },
+            NodeRecord { title: "Mercury".to_string(), length: Some(100), disambiguation: Some(true), ..NodeRecord::default() }
+        ]);
+
+        let statistics = SegmentsMetric.compute(&AnalyzedNetwork::new(&network, Some(&nodes)));
+
+        assert_eq!(statistics["articles"]["numberOfArticles"], 2);
+        assert_eq!(statistics["articles"]["meanOutDegree"], 1.5);
+        assert_eq!(statistics["articles"]["meanInDegree"], 2.5);
+        assert_eq!(statistics["articles"]["meanLength"], 2500.0);
+        assert_eq!(statistics["articles"]["linkDensity"], 0.6);
+        assert_eq!(statistics["articles"]["meanTemplates"], 3.0);
+        assert_eq!(statistics["stubs"]["numberOfArticles"], 1);
+        assert_eq!(statistics["stubs"]["medianInDegree"], 2);
+        assert_eq!(statistics["lists"]["numberOfArticles"], 1);
+        assert_eq!(statistics["lists"]["medianOutDegree"], 3);
+        assert_eq!(statistics["disambiguationPages"]["numberOfArticles"], 1);
+    }
+}
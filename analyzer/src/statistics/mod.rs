@@ -1,39 +1,120 @@
-use std::collections::HashMap;
-use serde::Serialize;
+use std::{collections::HashMap, sync::OnceLock, thread};
+use serde_json::{Map, Value};
 
-use self::summary::{number_of_edges, number_of_nodes, calculate_out_degrees, calculate_in_degrees, find_max_degree, degree_histogram};
+use crate::node_attributes::NodeAttributes;
+
+use self::{indexed_network::IndexedNetwork, metrics::{BetweennessMetric, CitationsMetric, DegreesMetric, DisambiguationMetric, HitsMetric, KCoreMetric, PageRankMetric, PowerLawMetric, SegmentsMetric, SummaryMetric}};
 
 pub mod summary;
+pub mod indexed_network;
+pub mod page_rank;
+pub mod community;
+pub mod hits;
+pub mod betweenness;
+pub mod k_core;
+pub mod metrics;
+pub mod power_law;
+pub mod top_k;
+pub mod correlation;
+
+/// A statistic that can be computed for a network. The result of every metric ends up in the
+/// statistics document under the name of the metric.
+pub trait Metric: Sync {
+    fn name(&self) -> &'static str;
+
+    fn compute(&self, network: &AnalyzedNetwork) -> Value;
+
+    /// Whether the metric needs the node attributes of the network.
+    fn needs_node_attributes(&self) -> bool {
+        false
+    }
+}
+
+pub struct StatisticsOptions {
+    pub seed: u64,
+    pub betweenness_samples: usize,
+    pub power_law_bootstraps: usize,
+    pub number_of_top_nodes: usize,
+    pub ccdf: bool,
+    pub log_bins_per_decade: Option<usize>
+}
+
+impl Default for StatisticsOptions {
+    fn default() -> Self {
+        StatisticsOptions {
+            seed: 0,
+            betweenness_samples: 100,
+            power_law_bootstraps: 100,
+            number_of_top_nodes: 10,
+            ccdf: false,
+            log_bins_per_decade: None
+        }
+    }
+}
+
+/// The network under analysis. The indexed form is only built if a metric asks for it and is
+/// then shared by all metrics.
+pub struct AnalyzedNetwork<'a> {
+    pub network: &'a HashMap<String, Vec<String>>,
+    /// Attributes collected while the network was generated, if they are available.
+    pub nodes: Option<&'a NodeAttributes>,
+    indexed_network: OnceLock<IndexedNetwork<'a>>
+}
+
+impl<'a> AnalyzedNetwork<'a> {
+    pub fn new(network: &'a HashMap<String, Vec<String>>, nodes: Option<&'a NodeAttributes>) -> AnalyzedNetwork<'a> {
+        AnalyzedNetwork { network, nodes, indexed_network: OnceLock::new() }
+    }
 
-#[derive(Serialize, Debug)]
-#[serde(rename_all = "camelCase")]
-pub struct NetworkStatistics {
-    number_of_nodes: usize,
-    number_of_edges: usize,
-    nodes_of_max_out_degree: Vec<String>,
-    max_out_degree: usize,
-    nodes_of_max_in_degree: Vec<String>,
-    max_in_degree: usize,
-    out_degree_distribution: Vec<usize>,
-    in_degree_distribution: Vec<usize>
-}
-
-pub fn gather_statistics(network: &HashMap<String, Vec<String>>) -> NetworkStatistics {
-    let out_degrees = calculate_out_degrees(network);
-    let in_degrees = calculate_in_degrees(network);
-    let (nodes_of_max_out_degree, max_out_degree) = find_max_degree(&out_degrees);
-    let (nodes_of_max_in_degree, max_in_degree) = find_max_degree(&in_degrees);
-    let out_degree_distribution = degree_histogram(&out_degrees, max_out_degree);
-    let in_degree_distribution = degree_histogram(&in_degrees, max_in_degree);
-
-    NetworkStatistics {
-        number_of_nodes: number_of_nodes(network),
-        number_of_edges: number_of_edges(network),
-        nodes_of_max_out_degree,
-        max_out_degree,
-        nodes_of_max_in_degree,
-        max_in_degree,
-        out_degree_distribution,
-        in_degree_distribution
+    pub fn indexed(&self) -> &IndexedNetwork<'a> {
+        self.indexed_network.get_or_init(|| IndexedNetwork::new(self.network))
     }
-}
\ No newline at end of file
+}
+
+/// All known metrics, configured with `options`.
+pub fn registry(options: &StatisticsOptions) -> Vec<Box<dyn Metric>> {
+    vec![
+        Box::new(SummaryMetric),
+        Box::new(DegreesMetric {
+            ccdf: options.ccdf,
+            log_bins_per_decade: options.log_bins_per_decade,
+            number_of_top_nodes: options.number_of_top_nodes
+        }),
+        Box::new(PageRankMetric { number_of_top_nodes: options.number_of_top_nodes }),
+        Box::new(HitsMetric { number_of_top_nodes: options.number_of_top_nodes }),
+        Box::new(BetweennessMetric {
+            number_of_samples: options.betweenness_samples,
+            seed: options.seed,
+            number_of_top_nodes: options.number_of_top_nodes
+        }),
+        Box::new(KCoreMetric { number_of_top_nodes: options.number_of_top_nodes }),
+        Box::new(PowerLawMetric { number_of_bootstraps: options.power_law_bootstraps, seed: options.seed }),
+        Box::new(CitationsMetric { number_of_top_nodes: options.number_of_top_nodes }),
+        Box::new(DisambiguationMetric { number_of_top_nodes: options.number_of_top_nodes }),
+        Box::new(SegmentsMetric)
+    ]
+}
+
+pub fn metric_names() -> Vec<&'static str> {
+    registry(&StatisticsOptions::default()).iter()
+        .map(|metric| metric.name())
+        .collect()
+}
+
+/// Computes the given metrics in parallel, one thread per metric.
+pub fn gather_statistics(network: &HashMap<String, Vec<String>>, nodes: Option<&NodeAttributes>, metrics: &[Box<dyn Metric>]) -> Map<String, Value> {
+    let analyzed_network = AnalyzedNetwork::new(network, nodes);
+
+    thread::scope(|scope| {
+        let handles = metrics.iter()
+            .map(|metric| {
+                let analyzed_network = &analyzed_network;
+                scope.spawn(move || (metric.name().to_owned(), metric.compute(analyzed_network)))
+            })
+            .collect::<Vec<_>>();
+
+        handles.into_iter()
+            .map(|handle| handle.join().unwrap())
+            .collect()
+    })
+}
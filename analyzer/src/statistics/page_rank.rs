@@ -0,0 +1,51 @@
+use super::indexed_network::IndexedNetwork;
+
+pub const DAMPING_FACTOR: f64 = 0.85;
+const MAX_ITERATIONS: usize = 100;
+const TOLERANCE: f64 = 1e-10;
+
+/// Computes the PageRank of every node with the power method. The rank of nodes without
+/// outgoing links is spread evenly over the whole network.
+pub fn page_rank(network: &IndexedNetwork, damping_factor: f64) -> Vec<f64> {
+    if network.is_empty() {
+        return Vec::new();
+    }
+
+    let number_of_nodes = network.len();
+
+    let uniform = 1.0 / number_of_nodes as f64;
+    let mut ranks = vec![uniform; number_of_nodes];
+
+    for _ in 0..MAX_ITERATIONS {
+        let dangling_rank: f64 = (0..number_of_nodes)
+            .filter(|node| network.out_edges[*node].is_empty())
+            .map(|node| ranks[node])
+            .sum();
+
+        let base = (1.0 - damping_factor) * uniform + damping_factor * dangling_rank * uniform;
+        let mut new_ranks = vec![base; number_of_nodes];
+
+        for (node, targets) in network.out_edges.iter().enumerate() {
+            if !targets.is_empty() {
+                let share = damping_factor * ranks[node] / targets.len() as f64;
+
+                for target in targets {
+                    new_ranks[*target] += share;
+                }
+            }
+        }
+
+        let change: f64 = ranks.iter()
+            .zip(new_ranks.iter())
+            .map(|(old, new)| (old - new).abs())
+            .sum();
+
+        ranks = new_ranks;
+
+        if change < TOLERANCE {
+            break;
+        }
+    }
+
+    ranks
+}
@@ -0,0 +1,420 @@
+use std::f64::consts::{PI, SQRT_2};
+
+use rand::{rngs::StdRng, Rng, SeedableRng};
+use serde::Serialize;
+
+/// Tails with fewer observations are not considered when searching for `x_min`.
+const MIN_TAIL_OBSERVATIONS: usize = 10;
+const GOLDEN_SECTION_ITERATIONS: usize = 80;
+const MAX_ALPHA: f64 = 20.0;
+const NELDER_MEAD_ITERATIONS: usize = 300;
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct PowerLawFit {
+    pub x_min: usize,
+    pub alpha: f64,
+    pub alpha_error: f64,
+    pub ks_statistic: f64,
+    pub p_value: Option<f64>,
+    pub number_of_bootstraps: usize,
+    pub number_of_observations: usize,
+    pub number_of_tail_observations: usize,
+    /// Hurwitz zeta of `alpha` and `x_min`, the expected number of nodes with degree `x` in the
+    /// tail is `number_of_tail_observations * x^-alpha / normalization`.
+    pub normalization: f64,
+    pub lognormal: LognormalComparison
+}
+
+#[derive(Serialize, Debug)]
+#[serde(rename_all = "camelCase")]
+pub struct LognormalComparison {
+    pub mu: f64,
+    pub sigma: f64,
+    /// Log-likelihood of the power law minus the log-likelihood of the lognormal, positive
+    /// values favour the power law.
+    pub log_likelihood_ratio: f64,
+    pub normalized_ratio: f64,
+    /// Probability of a ratio at least this far from zero if both fits were equally good.
+    pub p_value: f64
+}
+
+struct ScanResult {
+    x_min_index: usize,
+    alpha: f64,
+    ks_statistic: f64
+}
+
+/// Fits a discrete power law to the tail of `histogram`, a list of `(value, count)` pairs sorted by
+/// value, following Clauset, Shalizi and Newman: `alpha` is the maximum likelihood estimate for a
+/// given `x_min`, and `x_min` is chosen to minimise the Kolmogorov-Smirnov distance between the tail
+/// and the fit. The goodness of fit is estimated from `number_of_bootstraps` semi-parametric
+/// bootstrap samples. Values below one are ignored.
+pub fn fit_power_law(histogram: &[(usize, usize)], number_of_bootstraps: usize, seed: u64) -> Option<PowerLawFit> {
+    let histogram = histogram.iter()
+        .filter(|(value, count)| *value >= 1 && *count > 0)
+        .copied()
+        .collect::<Vec<(usize, usize)>>();
+
+    let scan = scan_x_min(&histogram)?;
+    let tail = &histogram[scan.x_min_index..];
+    let x_min = tail[0].0;
+    let number_of_observations = histogram.iter().map(|(_, count)| count).sum::<usize>();
+    let number_of_tail_observations = tail.iter().map(|(_, count)| count).sum::<usize>();
+
+    let p_value = (number_of_bootstraps > 0).then(|| {
+        let mut rng = StdRng::seed_from_u64(seed);
+        let below = &histogram[..scan.x_min_index];
+        let mut number_of_worse_fits = 0;
+
+        for _ in 0..number_of_bootstraps {
+            let synthetic = synthetic_histogram(below, number_of_observations, number_of_tail_observations, x_min, scan.alpha, &mut rng);
+
+            if scan_x_min(&synthetic).is_some_and(|synthetic_scan| synthetic_scan.ks_statistic >= scan.ks_statistic) {
+                number_of_worse_fits += 1;
+            }
+        }
+
+        number_of_worse_fits as f64 / number_of_bootstraps as f64
+    });
+
+    Some(PowerLawFit {
+        x_min,
+        alpha: scan.alpha,
+        alpha_error: (scan.alpha - 1.0) / (number_of_tail_observations as f64).sqrt(),
+        ks_statistic: scan.ks_statistic,
+        p_value,
+        number_of_bootstraps,
+        number_of_observations,
+        number_of_tail_observations,
+        normalization: hurwitz_zeta(scan.alpha, x_min as f64),
+        lognormal: compare_with_lognormal(tail, scan.alpha)
+    })
+}
+
+fn scan_x_min(histogram: &[(usize, usize)]) -> Option<ScanResult> {
+    // suffix sums of the number of observations and of their logarithms
+    let mut tail_counts = vec![0; histogram.len() + 1];
+    let mut tail_log_sums = vec![0.0; histogram.len() + 1];
+
+    for index in (0..histogram.len()).rev() {
+        let (value, count) = histogram[index];
+        tail_counts[index] = tail_counts[index + 1] + count;
+        tail_log_sums[index] = tail_log_sums[index + 1] + count as f64 * (value as f64).ln();
+    }
+
+    let mut best: Option<ScanResult> = None;
+
+    // the last distinct value can not be fitted, a power law needs at least two values
+    for x_min_index in 0..histogram.len().saturating_sub(1) {
+        let number_of_tail_observations = tail_counts[x_min_index];
+
+        if number_of_tail_observations < MIN_TAIL_OBSERVATIONS {
+            break;
+        }
+
+        let tail = &histogram[x_min_index..];
+        let alpha = estimate_alpha(tail[0].0, number_of_tail_observations, tail_log_sums[x_min_index]);
+        let ks_statistic = ks_distance(tail, number_of_tail_observations, alpha);
+
+        if best.as_ref().is_none_or(|best| ks_statistic < best.ks_statistic) {
+            best = Some(ScanResult { x_min_index, alpha, ks_statistic });
+        }
+    }
+
+    best
+}
+
+/// Maximises the discrete power law log-likelihood `-n ln zeta(alpha, x_min) - alpha sum(ln x)`,
+/// which is concave in `alpha`, with a golden section search.
+fn estimate_alpha(x_min: usize, number_of_observations: usize, log_sum: f64) -> f64 {
+    let log_likelihood = |alpha: f64| {
+        -(number_of_observations as f64) * hurwitz_zeta(alpha, x_min as f64).ln() - alpha * log_sum
+    };
+
+    let ratio = (5.0_f64.sqrt() - 1.0) / 2.0;
+    let mut lower = 1.0 + 1e-6;
+    let mut upper = MAX_ALPHA;
+    let mut left = upper - ratio * (upper - lower);
+    let mut right = lower + ratio * (upper - lower);
+    let mut left_value = log_likelihood(left);
+    let mut right_value = log_likelihood(right);
+
+    for _ in 0..GOLDEN_SECTION_ITERATIONS {
+        if left_value > right_value {
+            upper = right;
+            right = left;
+            right_value = left_value;
+            left = upper - ratio * (upper - lower);
+            left_value = log_likelihood(left);
+        } else {
+            lower = left;
+            left = right;
+            left_value = right_value;
+            right = lower + ratio * (upper - lower);
+            right_value = log_likelihood(right);
+        }
+    }
+
+    (lower + upper) / 2.0
+}
+
+/// Largest distance between the empirical and the fitted cumulative distribution of the tail.
+fn ks_distance(tail: &[(usize, usize)], number_of_observations: usize, alpha: f64) -> f64 {
+    let x_min = tail[0].0 as f64;
+    let normalization = hurwitz_zeta(alpha, x_min);
+    let mut distance: f64 = 0.0;
+    let mut remaining = number_of_observations;
+    // P(X >= value) of the fit, updated incrementally while walking through the tail
+    let mut model_tail = 1.0;
+    let mut current_value = tail[0].0;
+
+    for (value, count) in tail {
+        model_tail = advance_model_tail(model_tail, current_value, *value, alpha, normalization);
+        current_value = *value;
+
+        // before the jump at value the empirical tail still contains all remaining observations
+        let empirical_tail = remaining as f64 / number_of_observations as f64;
+        distance = distance.max((empirical_tail - model_tail).abs());
+
+        remaining -= count;
+        let empirical_tail = remaining as f64 / number_of_observations as f64;
+        let model_tail_after = model_tail - (*value as f64).powf(-alpha) / normalization;
+        distance = distance.max((empirical_tail - model_tail_after).abs());
+    }
+
+    distance
+}
+
+fn advance_model_tail(model_tail: f64, from: usize, to: usize, alpha: f64, normalization: f64) -> f64 {
+    if to - from <= 64 {
+        model_tail - (from..to).map(|value| (value as f64).powf(-alpha)).sum::<f64>() / normalization
+    } else {
+        hurwitz_zeta(alpha, to as f64) / normalization
+    }
+}
+
+/// Draws a data set of the same size as the original one: with the probability of the tail a
+/// value is drawn from the fitted power law, otherwise from the observed values below `x_min`.
+fn synthetic_histogram(below: &[(usize, usize)], number_of_observations: usize, number_of_tail_observations: usize, x_min: usize, alpha: f64, rng: &mut StdRng) -> Vec<(usize, usize)> {
+    let number_of_below = number_of_observations - number_of_tail_observations;
+    let cumulative_below = below.iter()
+        .scan(0, |total, (_, count)| {
+            *total += count;
+            Some(*total)
+        })
+        .collect::<Vec<usize>>();
+
+    let mut values = Vec::with_capacity(number_of_observations);
+
+    for _ in 0..number_of_observations {
+        if number_of_below > 0 && rng.gen_range(0..number_of_observations) < number_of_below {
+            let position = rng.gen_range(0..number_of_below);
+            let index = cumulative_below.partition_point(|total| *total <= position);
+            values.push(below[index].0);
+        } else {
+            let uniform: f64 = rng.gen();
+            let value = (x_min as f64 - 0.5) * (1.0 - uniform).powf(-1.0 / (alpha - 1.0)) + 0.5;
+            values.push(value.floor() as usize);
+        }
+    }
+
+    values.sort_unstable();
+
+    let mut histogram: Vec<(usize, usize)> = Vec::new();
+    for value in values {
+        match histogram.last_mut() {
+            Some((last, count)) if *last == value => *count += 1,
+            _ => histogram.push((value, 1))
+        }
+    }
+
+    histogram
+}
+
+/// Fits a discretised lognormal truncated at `x_min` to the tail and compares it with the power
+/// law with Vuong's likelihood ratio test.
+fn compare_with_lognormal(tail: &[(usize, usize)], alpha: f64) -> LognormalComparison {
+    let x_min = tail[0].0;
+    let number_of_observations = tail.iter().map(|(_, count)| count).sum::<usize>() as f64;
+
+    let mean_log = tail.iter().map(|(value, count)| *count as f64 * (*value as f64).ln()).sum::<f64>() / number_of_observations;
+    let variance_log = tail.iter().map(|(value, count)| *count as f64 * ((*value as f64).ln() - mean_log).powi(2)).sum::<f64>() / number_of_observations;
+
+    let negative_log_likelihood = |parameters: [f64; 2]| {
+        let sigma = parameters[1].exp();
+        -tail.iter()
+            .map(|(value, count)| *count as f64 * lognormal_log_probability(*value, x_min, parameters[0], sigma))
+            .sum::<f64>()
+    };
+
+    let [mu, log_sigma] = nelder_mead(negative_log_likelihood, [mean_log, variance_log.sqrt().max(0.1).ln()]);
+    let sigma = log_sigma.exp();
+
+    let power_law_normalization = hurwitz_zeta(alpha, x_min as f64).ln();
+    let differences = tail.iter()
+        .map(|(value, count)| {
+            let power_law = -alpha * (*value as f64).ln() - power_law_normalization;
+            let lognormal = lognormal_log_probability(*value, x_min, mu, sigma);
+            (power_law - lognormal, *count as f64)
+        })
+        .collect::<Vec<(f64, f64)>>();
+
+    let log_likelihood_ratio = differences.iter().map(|(difference, count)| difference * count).sum::<f64>();
+    let mean_difference = log_likelihood_ratio / number_of_observations;
+    let variance = differences.iter()
+        .map(|(difference, count)| count * (difference - mean_difference).powi(2))
+        .sum::<f64>() / number_of_observations;
+
+    let (normalized_ratio, p_value) = if variance > 0.0 {
+        let normalized_ratio = log_likelihood_ratio / (variance * number_of_observations).sqrt();
+        (normalized_ratio, erfc(normalized_ratio.abs() / SQRT_2))
+    } else {
+        (0.0, 1.0)
+    };
+
+    LognormalComparison { mu, sigma, log_likelihood_ratio, normalized_ratio, p_value }
+}
+
+/// Logarithm of the probability of `value` under a lognormal distribution discretised to the
+/// integers and truncated below `x_min`.
+fn lognormal_log_probability(value: usize, x_min: usize, mu: f64, sigma: f64) -> f64 {
+    let standardize = |x: f64| (x.ln() - mu) / sigma;
+    let log_normalization = log_upper_tail(standardize(x_min as f64 - 0.5));
+    let lower = standardize(value as f64 - 0.5);
+    let upper = standardize(value as f64 + 0.5);
+
+    if upper - lower > 1e-3 {
+        let mass = upper_tail(lower) - upper_tail(upper);
+
+        if mass > 0.0 {
+            return mass.ln() - log_normalization;
+        }
+    }
+
+    // for narrow intervals the density at the value is accurate and avoids cancellation
+    let z = standardize(value as f64);
+    -z * z / 2.0 - (2.0 * PI).sqrt().ln() - sigma.ln() - (value as f64).ln() - log_normalization
+}
+
+/// P(Z > z) for a standard normal Z.
+fn upper_tail(z: f64) -> f64 {
+    erfc(z / SQRT_2) / 2.0
+}
+
+fn log_upper_tail(z: f64) -> f64 {
+    if z < 8.0 {
+        upper_tail(z).ln()
+    } else {
+        -z * z / 2.0 - (z * (2.0 * PI).sqrt()).ln()
+    }
+}
+
+/// Complementary error function with a fractional error below 1.2e-7 (Numerical Recipes).
+fn erfc(x: f64) -> f64 {
+    let z = x.abs();
+    let t = 1.0 / (1.0 + 0.5 * z);
+    let result = t * (-z * z - 1.26551223 + t * (1.00002368 + t * (0.37409196 + t * (0.09678418 + t * (-0.18628806
+        + t * (0.27886807 + t * (-1.13520398 + t * (1.48851587 + t * (-0.82215223 + t * 0.17087277))))))))).exp();
+
+    if x >= 0.0 { result } else { 2.0 - result }
+}
+
+/// Hurwitz zeta function `sum_{k >= 0} (q + k)^-s` for `s > 1` and `q >= 1`, evaluated with the
+/// Euler-Maclaurin formula.
+pub fn hurwitz_zeta(s: f64, q: f64) -> f64 {
+    const DIRECT_TERMS: usize = 9;
+    const BERNOULLI: [f64; 6] = [1.0 / 6.0, -1.0 / 30.0, 1.0 / 42.0, -1.0 / 30.0, 5.0 / 66.0, -691.0 / 2730.0];
+
+    let mut sum = (0..DIRECT_TERMS).map(|k| (q + k as f64).powf(-s)).sum::<f64>();
+    let a = q + DIRECT_TERMS as f64;
+
+    sum += a.powf(1.0 - s) / (s - 1.0) + 0.5 * a.powf(-s);
+
+    let mut term = s * a.powf(-s - 1.0);
+    let mut factorial = 2.0;
+    sum += BERNOULLI[0] / factorial * term;
+
+    for (j, bernoulli) in BERNOULLI.iter().enumerate().skip(1) {
+        let j = j as f64;
+        term *= (s + 2.0 * j - 1.0) * (s + 2.0 * j) / (a * a);
+        factorial *= (2.0 * j + 1.0) * (2.0 * j + 2.0);
+        sum += bernoulli / factorial * term;
+    }
+
+    sum
+}
+
+/// Minimises `function` with the Nelder-Mead simplex method.
+fn nelder_mead<F: Fn([f64; 2]) -> f64>(function: F, start: [f64; 2]) -> [f64; 2] {
+    let mut simplex = [start, [start[0] + 0.5, start[1]], [start[0], start[1] + 0.5]]
+        .map(|point| (point, function(point)));
+
+    for _ in 0..NELDER_MEAD_ITERATIONS {
+        simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+
+        if (simplex[2].1 - simplex[0].1).abs() < 1e-10 {
+            break;
+        }
+
+        let centroid = [(simplex[0].0[0] + simplex[1].0[0]) / 2.0, (simplex[0].0[1] + simplex[1].0[1]) / 2.0];
+        let towards = |factor: f64| [
+            centroid[0] + factor * (simplex[2].0[0] - centroid[0]),
+            centroid[1] + factor * (simplex[2].0[1] - centroid[1])
+        ];
+
+        let reflected = towards(-1.0);
+        let reflected_value = function(reflected);
+
+        if reflected_value < simplex[0].1 {
+            let expanded = towards(-2.0);
+            let expanded_value = function(expanded);
+            simplex[2] = if expanded_value < reflected_value { (expanded, expanded_value) } else { (reflected, reflected_value) };
+        } else if reflected_value < simplex[1].1 {
+            simplex[2] = (reflected, reflected_value);
+        } else {
+            let contracted = towards(0.5);
+            let contracted_value = function(contracted);
+
+            if contracted_value < simplex[2].1 {
+                simplex[2] = (contracted, contracted_value);
+            } else {
+                let best = simplex[0].0;
+                for vertex in simplex.iter_mut().skip(1) {
+                    let point = [(vertex.0[0] + best[0]) / 2.0, (vertex.0[1] + best[1]) / 2.0];
+                    *vertex = (point, function(point));
+                }
+            }
+        }
+    }
+
+    simplex.sort_by(|a, b| a.1.total_cmp(&b.1));
+    simplex[0].0
+}
+
+#[cfg(test)]
+mod tests {
+    use std::f64::consts::PI;
+
+    use rand::{rngs::StdRng, SeedableRng};
+
+    use super::{fit_power_law, hurwitz_zeta, synthetic_histogram};
+
+    #[test]
+    fn test_hurwitz_zeta() {
+        assert!((hurwitz_zeta(2.0, 1.0) - PI * PI / 6.0).abs() < 1e-12);
+        assert!((hurwitz_zeta(2.0, 3.0) - (PI * PI / 6.0 - 1.0 - 0.25)).abs() < 1e-12);
+    }
+
+    #[test]
+    fn test_fit_recovers_alpha() {
+        let mut rng = StdRng::seed_from_u64(1);
+        let histogram = synthetic_histogram(&[], 20000, 20000, 1, 2.5, &mut rng);
+
+        let fit = fit_power_law(&histogram, 0, 0).unwrap();
+
+        assert!((fit.alpha - 2.5).abs() < 0.1, "alpha was {}", fit.alpha);
+        assert!(fit.x_min <= 5, "x_min was {}", fit.x_min);
+        assert!(fit.lognormal.log_likelihood_ratio > -10.0);
+    }
+}
@@ -25,7 +25,7 @@ pub fn calculate_out_degrees(network: &HashMap<String, Vec<String>>) -> HashMap<
 }
 
 pub fn calculate_in_degrees(network: &HashMap<String, Vec<String>>) -> HashMap<&String, usize> {
-    let mut in_degrees = HashMap::with_capacity(network.capacity());
+    let mut in_degrees: HashMap<&String, usize> = HashMap::with_capacity(network.capacity());
 
     for (_, edges) in network {
         for linked_node in edges {
@@ -53,12 +53,60 @@ pub fn find_max_degree(degrees: &HashMap<&String, usize>) -> (Vec<String>, usize
     (nodes_of_max_degree, max_degree)
 }
 
-pub fn degree_histogram(degrees: &HashMap<&String, usize>, max_degree: usize) -> Vec<usize> {
-    let mut histogram = vec![0; max_degree + 1];
+/// Number of nodes per degree as `(degree, count)` pairs sorted by degree. Degrees without any
+/// node are left out.
+pub fn degree_histogram(degrees: &HashMap<&String, usize>) -> Vec<(usize, usize)> {
+    let mut histogram = HashMap::new();
 
-    for (_, degree) in degrees {
-        histogram[*degree] = histogram[*degree] + 1;
+    for degree in degrees.values() {
+        *histogram.entry(*degree).or_insert(0) += 1;
     }
 
+    let mut histogram = histogram.into_iter().collect::<Vec<(usize, usize)>>();
+    histogram.sort_unstable();
+
     histogram
-}
\ No newline at end of file
+}
+
+/// Fraction of nodes with at least the given degree, for every degree of the histogram.
+pub fn complementary_cumulative_distribution(histogram: &[(usize, usize)]) -> Vec<(usize, f64)> {
+    let total = histogram.iter().map(|(_, count)| count).sum::<usize>() as f64;
+    let mut remaining = total;
+
+    histogram.iter()
+        .map(|(degree, count)| {
+            let fraction = remaining / total;
+            remaining -= *count as f64;
+            (*degree, fraction)
+        })
+        .collect()
+}
+
+/// Aggregates the histogram into logarithmically growing bins, `bins_per_decade` per factor of ten.
+/// Every bin is reported as its geometric center and the average number of nodes per degree in
+/// the bin. Nodes of degree zero can not be binned and are left out.
+pub fn logarithmic_bins(histogram: &[(usize, usize)], bins_per_decade: usize) -> Vec<(f64, f64)> {
+    let bin_of = |degree: usize| ((degree as f64).log10() * bins_per_decade as f64 + 1e-9).floor() as usize;
+    let bin_start = |bin: usize| 10f64.powf(bin as f64 / bins_per_decade as f64).ceil() as usize;
+
+    let mut bins: Vec<(usize, usize)> = Vec::new();
+
+    for (degree, count) in histogram.iter().filter(|(degree, _)| *degree > 0) {
+        let bin = bin_of(*degree);
+
+        match bins.last_mut() {
+            Some((last, total)) if *last == bin => *total += count,
+            _ => bins.push((bin, *count))
+        }
+    }
+
+    bins.into_iter()
+        .map(|(bin, count)| {
+            let start = bin_start(bin);
+            let end = bin_start(bin + 1).max(start + 1);
+            let center = ((start as f64) * (end as f64 - 1.0)).sqrt();
+
+            (center, count as f64 / (end - start) as f64)
+        })
+        .collect()
+}
@@ -0,0 +1,91 @@
+use std::{cmp::{Ordering, Reverse}, collections::BinaryHeap};
+
+use serde::Serialize;
+
+#[derive(Serialize, Debug, PartialEq)]
+#[serde(rename_all = "camelCase")]
+pub struct ScoredNode<S> {
+    pub title: String,
+    pub score: S
+}
+
+/// Heap entry ordered by score, ties are broken in favour of the alphabetically first title.
+struct Entry<'a, S> {
+    title: &'a str,
+    score: S
+}
+
+impl<S: PartialOrd> Ord for Entry<'_, S> {
+    fn cmp(&self, other: &Self) -> Ordering {
+        self.score.partial_cmp(&other.score)
+            .unwrap_or(Ordering::Equal)
+            .then_with(|| other.title.cmp(self.title))
+    }
+}
+
+impl<S: PartialOrd> PartialOrd for Entry<'_, S> {
+    fn partial_cmp(&self, other: &Self) -> Option<Ordering> {
+        Some(self.cmp(other))
+    }
+}
+
+impl<S: PartialOrd> PartialEq for Entry<'_, S> {
+    fn eq(&self, other: &Self) -> bool {
+        self.cmp(other) == Ordering::Equal
+    }
+}
+
+impl<S: PartialOrd> Eq for Entry<'_, S> {}
+
+/// The `k` nodes with the highest scores in descending order. Only `k` entries are kept in memory
+/// at any time, so this is cheap even for scores of every article.
+pub fn top_k<'a, S, I>(scores: I, k: usize) -> Vec<ScoredNode<S>>
+    where S: PartialOrd, I: IntoIterator<Item = (&'a str, S)> {
+    if k == 0 {
+        return Vec::new();
+    }
+
+    let mut heap = BinaryHeap::with_capacity(k);
+
+    for (title, score) in scores {
+        let entry = Reverse(Entry { title, score });
+
+        if heap.len() < k {
+            heap.push(entry);
+        } else if heap.peek().is_some_and(|smallest| entry < *smallest) {
+            heap.pop();
+            heap.push(entry);
+        }
+    }
+
+    heap.into_sorted_vec()
+        .into_iter()
+        .map(|Reverse(entry)| ScoredNode { title: entry.title.to_owned(), score: entry.score })
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use super::{top_k, ScoredNode};
+
+    #[test]
+    fn test_top_k_keeps_highest_scores_in_order() {
+        let scores = vec![("A", 3), ("B", 7), ("C", 1), ("D", 7), ("E", 5)];
+
+        let top = top_k(scores, 3);
+
+        assert_eq!(top, vec![
+            ScoredNode { title: "B".to_owned(), score: 7 },
+            ScoredNode { title: "D".to_owned(), score: 7 },
+            ScoredNode { title: "E".to_owned(), score: 5 }
+        ]);
+    }
+
+    #[test]
+    fn test_top_k_with_fewer_nodes_than_k() {
+        let top = top_k(vec![("A", 0.5), ("B", 1.5)], 5);
+
+        assert_eq!(top.len(), 2);
+        assert_eq!(top[0].title, "B");
+    }
+}
@@ -0,0 +1,200 @@
+use std::{collections::{HashMap, HashSet, VecDeque}, fs::File, io::{BufRead, BufReader, Result}, path::PathBuf};
+
+use crate::{analyze::load_network, categories::{articles_below, category_title}, export::{computed_attributes, page_id_attribute}, graph_format::{write_network, GraphFormat}, network_generator::wiki_text::canonical_category_title, statistics::indexed_network::IndexedNetwork};
+
+#[derive(Clone, Copy, PartialEq, Debug)]
+pub enum Direction {
+    In, Out, Both
+}
+
+impl Direction {
+    pub const NAMES: [&'static str; 3] = ["in", "out", "both"];
+
+    pub fn from_name(name: &str) -> Option<Direction> {
+        match name {
+            "in" => Some(Direction::In),
+            "out" => Some(Direction::Out),
+            "both" => Some(Direction::Both),
+            _ => None
+        }
+    }
+}
+
+/// Which articles end up in the subgraph.
+pub enum Selection {
+    /// All articles within `hops` links of one of the `titles`.
+    Ego { titles: Vec<String>, hops: usize, direction: Direction },
+    /// The articles listed in a file, one title per line.
+    TitleFile(PathBuf),
+    /// All articles which are directly in the category. `category_namespace` is the local name of
+    /// the category namespace, which the links in the network are written with.
+    Category { category: String, category_namespace: String },
+    /// All articles in the category or its subcategories, at most `depth` levels below it, taken
+    /// from the category graph of the network.
+    CategoryTree { category: String, category_namespace: String, category_file_path: PathBuf, depth: Option<usize> }
+}
+
+pub struct SubgraphOutput {
+    pub format: GraphFormat,
+    pub attribute_names: Vec<String>,
+    pub xml_dump_index_path: Option<PathBuf>
+}
+
+pub fn subgraph(network_file_path: PathBuf, output_path: PathBuf, selection: Selection, output: SubgraphOutput) -> Result<()> {
+    let network = load_network(network_file_path)?;
+
+    let selected_nodes = match selection {
+        Selection::Ego { titles, hops, direction } => ego_network(&network, &titles, hops, direction),
+        Selection::TitleFile(title_file_path) => read_titles(title_file_path)?,
+        Selection::Category { category, category_namespace } => category_members(&network, &category, &category_namespace),
+        Selection::CategoryTree { category, category_namespace, category_file_path, depth } => {
+            articles_below(&load_network(category_file_path)?, &category, &category_namespace, depth).into_iter().collect()
+        }
+    };
+
+    let subgraph = induced_subgraph(&network, &selected_nodes);
+    println!("extracted {} articles", subgraph.len());
+
+    let indexed_subgraph = IndexedNetwork::new(&subgraph);
+    let mut attributes = Vec::new();
+
+    if let Some(xml_dump_index_path) = output.xml_dump_index_path {
+        attributes.push(page_id_attribute(&indexed_subgraph, &xml_dump_index_path)?);
+    }
+
+    attributes.extend(computed_attributes(&indexed_subgraph, &output.attribute_names));
+
+    write_network(output.format, &indexed_subgraph, &attributes, output_path)
+}
+
+/// Breadth first search from all `titles` at once, following links in the given direction.
+pub fn ego_network(network: &HashMap<String, Vec<String>>, titles: &[String], hops: usize, direction: Direction) -> HashSet<String> {
+    let mut incoming_links: HashMap<&String, Vec<&String>> = HashMap::new();
+
+    if direction != Direction::Out {
+        for (node, links) in network {
+            for link in links {
+                incoming_links.entry(link).or_default().push(node);
+            }
+        }
+    }
+
+    let mut visited = HashSet::new();
+    let mut queue = VecDeque::new();
+
+    for title in titles.iter().filter(|title| network.contains_key(*title)) {
+        if visited.insert(title.to_owned()) {
+            queue.push_back((title, 0));
+        }
+    }
+
+    while let Some((node, distance)) = queue.pop_front() {
+        if distance == hops {
+            continue;
+        }
+
+        let outgoing = network.get(node).into_iter()
+            .flatten()
+            .filter(|_| direction != Direction::In);
+        let incoming = incoming_links.get(node).into_iter()
+            .flatten()
+            .copied();
+
+        for neighbour in outgoing.chain(incoming) {
+            if network.contains_key(neighbour) && visited.insert(neighbour.to_owned()) {
+                queue.push_back((neighbour, distance + 1));
+            }
+        }
+    }
+
+    visited
+}
+
+/// Articles linking to the category page, which is how category membership is written in
+/// wikitext. The links are written with the local name of the category namespace.
+pub fn category_members(network: &HashMap<String, Vec<String>>, category: &str, category_namespace: &str) -> HashSet<String> {
+    let category_title = Some(category_title(category, category_namespace));
+
+    network.iter()
+        .filter(|(_, links)| links.iter().any(|link| canonical_category_title(link, category_namespace) == category_title))
+        .map(|(node, _)| node.to_owned())
+        .collect()
+}
+
+fn read_titles(title_file_path: PathBuf) -> Result<HashSet<String>> {
+    let reader = BufReader::new(File::open(title_file_path)?);
+
+    reader.lines()
+        .map(|line| line.map(|title| title.trim().to_owned()))
+        .filter(|title| title.as_ref().map_or(true, |title| !title.is_empty()))
+        .collect()
+}
+
+/// The selected articles together with all links between them.
+pub fn induced_subgraph(network: &HashMap<String, Vec<String>>, nodes: &HashSet<String>) -> HashMap<String, Vec<String>> {
+    nodes.iter()
+        .filter_map(|node| network.get(node).map(|links| (node, links)))
+        .map(|(node, links)| (node.to_owned(), links.iter()
+            .filter(|link| nodes.contains(*link))
+            .map(|link| link.to_owned())
+            .collect()))
+        .collect()
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::{HashMap, HashSet};
+
+    use super::{category_members, ego_network, induced_subgraph, Direction};
+
+    fn network() -> HashMap<String, Vec<String>> {
+        HashMap::from([
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec!["C".to_string()]),
+            ("C".to_string(), vec!["D".to_string()]),
+            ("D".to_string(), vec![]),
+            ("E".to_string(), vec!["B".to_string(), "Missing".to_string()])
+        ])
+    }
+
+    fn titles(titles: &[&str]) -> HashSet<String> {
+        titles.iter().map(|title| title.to_string()).collect()
+    }
+
+    #[test]
+    fn test_ego_network() {
+        let network = network();
+        let ego = vec!["B".to_string()];
+
+        assert_eq!(ego_network(&network, &ego, 0, Direction::Both), titles(&["B"]));
+        assert_eq!(ego_network(&network, &ego, 1, Direction::Out), titles(&["B", "C"]));
+        assert_eq!(ego_network(&network, &ego, 2, Direction::Out), titles(&["B", "C", "D"]));
+        assert_eq!(ego_network(&network, &ego, 1, Direction::In), titles(&["A", "B", "E"]));
+        assert_eq!(ego_network(&network, &ego, 1, Direction::Both), titles(&["A", "B", "C", "E"]));
+        assert!(ego_network(&network, &["Missing".to_string()], 1, Direction::Both).is_empty());
+    }
+
+    #[test]
+    fn test_category_members() {
+        let network = HashMap::from([
+            ("Köln".to_string(), vec!["Rhein".to_string(), "Kategorie:Stadt in Deutschland".to_string()]),
+            ("Bonn".to_string(), vec!["Category:Stadt in Deutschland".to_string()]),
+            ("Rhein".to_string(), vec![":Kategorie:Stadt in Deutschland".to_string()])
+        ]);
+
+        assert_eq!(category_members(&network, "Stadt in Deutschland", "Kategorie"), titles(&["Bonn", "Köln"]));
+        assert_eq!(category_members(&network, "Kategorie:Stadt in Deutschland", "Kategorie"), titles(&["Bonn", "Köln"]));
+        assert_eq!(category_members(&network, "Stadt in Deutschland", "Category"), titles(&["Bonn"]));
+    }
+
+    #[test]
+    fn test_induced_subgraph() {
+        let subgraph = induced_subgraph(&network(), &titles(&["A", "B", "D", "Missing"]));
+
+        assert_eq!(subgraph, HashMap::from([
+            ("A".to_string(), vec!["B".to_string()]),
+            ("B".to_string(), vec![]),
+            ("D".to_string(), vec![])
+        ]));
+    }
+}
@@ -0,0 +1,116 @@
+use std::{collections::{HashMap, HashSet}, io::Result, path::PathBuf};
+
+use crate::{analyze::load_network, common::{find_entries, read_from}, network_generator::wiki_text::unlinked_text};
+
+/// Weight of an article which links to the given article without being linked back.
+const RECIPROCITY_WEIGHT: f64 = 1.0;
+/// Weight of an article whose title appears in the wikitext outside of a link.
+const MENTION_WEIGHT: f64 = 2.0;
+
+#[derive(Debug, Default)]
+struct Suggestion {
+    co_citations: usize,
+    adamic_adar: f64,
+    links_back: bool,
+    unlinked_mentions: usize
+}
+
+impl Suggestion {
+    fn score(&self) -> f64 {
+        self.adamic_adar
+            + if self.links_back { RECIPROCITY_WEIGHT } else { 0.0 }
+            + if self.unlinked_mentions > 0 { MENTION_WEIGHT } else { 0.0 }
+    }
+}
+
+pub fn suggest_links(network_file_path: PathBuf, xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String, number_of_suggestions: usize) -> Result<()> {
+    let network = load_network(network_file_path)?;
+
+    let Some(linked) = network.get(title) else {
+        println!("No article with title {title} in the network!");
+        return Ok(());
+    };
+
+    let linked = linked.iter().collect::<HashSet<&String>>();
+    let mut suggestions: HashMap<&String, Suggestion> = HashMap::new();
+
+    // articles which link to the given article, and all other articles they link to
+    for (citing, links) in &network {
+        let links = links.iter().collect::<HashSet<&String>>();
+
+        if citing == title || !links.contains(title) {
+            continue;
+        }
+
+        if !linked.contains(citing) {
+            suggestions.entry(citing).or_default().links_back = true;
+        }
+
+        let weight = 1.0 / (links.len() as f64).ln();
+
+        for co_cited in links {
+            if co_cited != title && !linked.contains(co_cited) && network.contains_key(co_cited) {
+                let suggestion = suggestions.entry(co_cited).or_default();
+                suggestion.co_citations += 1;
+                suggestion.adamic_adar += weight;
+            }
+        }
+    }
+
+    if let Some(text) = read_wikitext(xml_dump_path, xml_dump_index_path, title)? {
+        let unlinked_text = unlinked_text(&text);
+
+        for (candidate, suggestion) in suggestions.iter_mut() {
+            suggestion.unlinked_mentions = count_mentions(&unlinked_text, candidate);
+        }
+    }
+
+    let mut ranked = suggestions.into_iter().collect::<Vec<(&String, Suggestion)>>();
+    ranked.sort_by(|(a_title, a), (b_title, b)| b.score().total_cmp(&a.score()).then_with(|| a_title.cmp(b_title)));
+
+    println!("rank\ttitle\tscore\tco-citations\tadamic-adar\tlinks back\tunlinked mentions");
+
+    for (rank, (candidate, suggestion)) in ranked.iter().take(number_of_suggestions).enumerate() {
+        println!("{}\t{}\t{:.3}\t{}\t{:.3}\t{}\t{}", rank + 1, candidate, suggestion.score(), suggestion.co_citations,
+            suggestion.adamic_adar, suggestion.links_back, suggestion.unlinked_mentions);
+    }
+
+    Ok(())
+}
+
+fn read_wikitext(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String) -> Result<Option<String>> {
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                return Ok(page.text);
+            }
+        }
+    }
+
+    Ok(None)
+}
+
+/// Number of occurrences of `title` in `text` which are not part of a longer word.
+fn count_mentions(text: &str, title: &str) -> usize {
+    text.match_indices(title)
+        .filter(|(start, _)| {
+            let before = text[..*start].chars().next_back();
+            let after = text[start + title.len()..].chars().next();
+            !before.is_some_and(char::is_alphanumeric) && !after.is_some_and(char::is_alphanumeric)
+        })
+        .count()
+}
+
+#[cfg(test)]
+mod tests {
+    use crate::network_generator::wiki_text::unlinked_text;
+
+    use super::count_mentions;
+
+    #[test]
+    fn test_count_unlinked_mentions() {
+        let text = unlinked_text("Rust is a language. See [[Rust]] and [[Rust (fungus)|rust]], not Rustacean.");
+
+        assert_eq!(count_mentions(&text, "Rust"), 1);
+    }
+}
@@ -0,0 +1,70 @@
+use std::{collections::BTreeMap, fs::File, io::{stdout, BufWriter, Result, Write}, path::PathBuf};
+
+use serde::Serialize;
+
+use crate::{common::{find_entries, process_dump, read_from}, network_generator::{wiki_text::{is_redirect, parse_templates, parse_text, Template, TemplateValue}, wiki_xml_dump::{Namespaces, WikiPage}}};
+
+#[derive(Serialize, Debug)]
+struct TemplateRecord<'a> {
+    title: &'a str,
+    template: &'a str,
+    params: &'a BTreeMap<String, TemplateValue>
+}
+
+/// The templates of an article whose names start with one of the lowercase `prefixes`, or all
+/// templates without prefixes.
+pub fn article_templates(page: &WikiPage, prefixes: &[String]) -> Vec<Template> {
+    page.text.as_deref()
+        .map(parse_templates)
+        .unwrap_or_default()
+        .into_iter()
+        .filter(|template| prefixes.is_empty() || template.has_prefix(prefixes))
+        .collect()
+}
+
+/// Writes one JSON object with title, template name and parameters per line.
+pub fn write_templates<'a, W: Write>(writer: &mut W, title: &str, templates: impl IntoIterator<Item = &'a Template>) -> Result<()> {
+    for template in templates {
+        serde_json::to_writer(&mut *writer, &TemplateRecord { title, template: &template.name, params: &template.params })?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+pub fn templates(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String, prefixes: &[String]) -> Result<()> {
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                return write_templates(&mut stdout(), &page.title, &article_templates(&page, prefixes));
+            }
+        }
+    }
+
+    println!("No article with title {title} found!");
+
+    Ok(())
+}
+
+pub fn all_templates(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, output_file_path: PathBuf, prefixes: &[String]) -> Result<()> {
+    let number_of_threads = 4;
+
+    let process = |page: WikiPage| {
+        if page.namespace_id != Namespaces::MAIN || parse_text(&page).is_none_or(|links| is_redirect(&links)) {
+            return None;
+        }
+
+        let templates = article_templates(&page, prefixes);
+        Some((page.title, templates))
+    };
+
+    process_dump(xml_dump_path, xml_dump_index_path, number_of_threads, process, |results| {
+        let mut writer = BufWriter::new(File::create(output_file_path)?);
+
+        for (title, templates) in results {
+            write_templates(&mut writer, &title, &templates)?;
+        }
+
+        writer.flush()
+    })
+}
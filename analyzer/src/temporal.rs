@@ -0,0 +1,371 @@
+use std::{collections::{BTreeSet, HashMap}, fs::File, io::{BufRead, BufReader, BufWriter, Error, ErrorKind, Result, Write}, path::{Path, PathBuf}, sync::mpsc::{channel, Sender}, thread};
+
+use crate::{common::read_revisions, network_generator::{canonicalize_link, wiki_text::{is_redirect, linked_articles, parse_text}, wiki_xml_dump::{Namespaces, WikiPage, WikiRevision}}};
+
+/// A link which existed from `added` until `removed`, or until the end of the history if it
+/// was never removed. Links which are removed and added again get one edge per interval.
+#[derive(Debug, PartialEq)]
+pub struct TemporalEdge {
+    pub source: String,
+    pub target: String,
+    pub added: String,
+    pub removed: Option<String>
+}
+
+/// A time in which a page was an article and no redirect, from `created` until `removed` or until
+/// the end of the history.
+#[derive(Debug, PartialEq)]
+pub struct PageInterval {
+    pub title: String,
+    pub created: String,
+    pub removed: Option<String>
+}
+
+enum TemporalRecord {
+    Edge(TemporalEdge),
+    Page(PageInterval)
+}
+
+/// Tracks the links of one page through its revisions.
+#[derive(Default)]
+struct LinkHistory {
+    title: String,
+    /// The current links with the timestamp of the revision which added them.
+    links: HashMap<String, String>,
+    /// The timestamp of the revision since which the page is an article.
+    article_since: Option<String>,
+    intervals: Vec<PageInterval>
+}
+
+impl LinkHistory {
+    fn new(title: String) -> LinkHistory {
+        LinkHistory { title, ..LinkHistory::default() }
+    }
+
+    /// Compares the links of the next revision with the current ones and returns the edges of
+    /// the removed links. Redirects have no links and end the interval in which the page was an
+    /// article.
+    fn update(&mut self, links: BTreeSet<String>, is_article: bool, timestamp: &str) -> Vec<TemporalEdge> {
+        match (&self.article_since, is_article) {
+            (None, true) => self.article_since = Some(timestamp.to_owned()),
+            (Some(_), false) => self.intervals.push(PageInterval {
+                title: self.title.to_owned(),
+                created: self.article_since.take().unwrap_or_default(),
+                removed: Some(timestamp.to_owned())
+            }),
+            _ => ()
+        }
+
+        let removed_links = self.links.keys()
+            .filter(|link| !links.contains(*link))
+            .cloned()
+            .collect::<Vec<String>>();
+
+        let edges = removed_links.into_iter()
+            .map(|link| {
+                let added = self.links.remove(&link).unwrap_or_default();
+                TemporalEdge { source: self.title.to_owned(), target: link, added, removed: Some(timestamp.to_owned()) }
+            })
+            .collect();
+
+        for link in links {
+            self.links.entry(link).or_insert_with(|| timestamp.to_owned());
+        }
+
+        edges
+    }
+
+    /// The edges of the links which exist in the last revision and the intervals in which the
+    /// page was an article.
+    fn finish(self) -> (Vec<TemporalEdge>, Vec<PageInterval>) {
+        let title = self.title;
+        let mut intervals = self.intervals;
+
+        if let Some(created) = self.article_since {
+            intervals.push(PageInterval { title: title.to_owned(), created, removed: None });
+        }
+
+        let edges = self.links.into_iter()
+            .map(|(target, added)| TemporalEdge { source: title.to_owned(), target, added, removed: None })
+            .collect();
+
+        (edges, intervals)
+    }
+}
+
+/// The file with the page intervals saved next to the temporal edges, e.g. `temporal.csv` gets
+/// `temporal.pages.csv`.
+pub fn pages_file_path(temporal_file_path: &Path) -> PathBuf {
+    temporal_file_path.with_extension("pages.csv")
+}
+
+/// Diffs the links of consecutive revisions of every article in the history dumps and saves
+/// when each link was added and removed as `source;target;added;removed` lines. When each page
+/// became an article and stopped being one is saved as `title;created;removed` lines next to it.
+pub fn temporal(xml_dump_paths: Vec<PathBuf>, output_path: PathBuf) -> Result<()> {
+    let number_of_threads = 4;
+    let (sender, receiver) = channel();
+
+    thread::scope(|scope| {
+        let handles = (0..number_of_threads).map(|thread_number| {
+            let sender = sender.clone();
+            let xml_dump_paths = xml_dump_paths.iter()
+                .skip(thread_number)
+                .step_by(number_of_threads)
+                .collect::<Vec<&PathBuf>>();
+
+            scope.spawn(move || -> Result<()> {
+                for xml_dump_path in xml_dump_paths {
+                    println!("reading revisions from {}", xml_dump_path.display());
+                    temporal_records(xml_dump_path, &sender)?;
+                    println!("finished reading revisions from {}", xml_dump_path.display());
+                }
+
+                Ok(())
+            })
+        }).collect::<Vec<_>>();
+
+        drop(sender);
+
+        let mut writer = BufWriter::with_capacity(16 * 1024 * 1024, File::create(&output_path)?);
+        let mut pages_writer = BufWriter::new(File::create(pages_file_path(&output_path))?);
+
+        for record in receiver {
+            match record {
+                TemporalRecord::Edge(edge) => write_temporal_edge(&mut writer, &edge)?,
+                TemporalRecord::Page(interval) => write_page_interval(&mut pages_writer, &interval)?
+            }
+        }
+
+        pages_writer.flush()?;
+        writer.flush()?;
+
+        for handle in handles {
+            handle.join().unwrap()?;
+        }
+
+        Ok(())
+    })
+}
+
+fn temporal_records(xml_dump_path: &PathBuf, sender: &Sender<TemporalRecord>) -> Result<()> {
+    let mut history = LinkHistory::default();
+
+    for revision in read_revisions(xml_dump_path)? {
+        if revision.namespace_id != Namespaces::MAIN {
+            continue;
+        }
+
+        if revision.title != history.title {
+            let finished = std::mem::replace(&mut history, LinkHistory::new(revision.title.to_owned()));
+
+            if !send_history(sender, finished) {
+                return Ok(());
+            }
+        }
+
+        if let Some((timestamp, links)) = revision_links(revision) {
+            let is_article = links.is_some();
+            let edges = history.update(links.unwrap_or_default(), is_article, &timestamp);
+
+            if !send_edges(sender, edges) {
+                return Ok(());
+            }
+        }
+    }
+
+    send_history(sender, history);
+
+    Ok(())
+}
+
+/// Sends the edges and returns whether the receiver still takes records.
+fn send_edges(sender: &Sender<TemporalRecord>, edges: Vec<TemporalEdge>) -> bool {
+    for edge in edges {
+        if sender.send(TemporalRecord::Edge(edge)).is_err() {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// Sends the edges and page intervals of a finished page and returns whether the receiver still
+/// takes records.
+fn send_history(sender: &Sender<TemporalRecord>, history: LinkHistory) -> bool {
+    if history.title.is_empty() {
+        return true;
+    }
+
+    let (edges, intervals) = history.finish();
+
+    if !send_edges(sender, edges) {
+        return false;
+    }
+
+    for interval in intervals {
+        if sender.send(TemporalRecord::Page(interval)).is_err() {
+            return false;
+        }
+    }
+
+    true
+}
+
+/// The articles linked in a revision, none for suppressed revisions. Redirects have no links,
+/// like in the network.
+fn revision_links(revision: WikiRevision) -> Option<(String, Option<BTreeSet<String>>)> {
+    let page = WikiPage { title: revision.title, text: Some(revision.text?), namespace_id: revision.namespace_id };
+    let links = parse_text(&page)?;
+
+    if is_redirect(&links) {
+        return Some((revision.timestamp, None));
+    }
+
+    Some((revision.timestamp, Some(linked_articles(&links).into_iter().filter_map(canonicalize_link).collect())))
+}
+
+fn write_temporal_edge<W: Write>(writer: &mut W, edge: &TemporalEdge) -> Result<()> {
+    writeln!(writer, "{};{};{};{}", edge.source, edge.target, edge.added, edge.removed.as_deref().unwrap_or_default())
+}
+
+fn write_page_interval<W: Write>(writer: &mut W, interval: &PageInterval) -> Result<()> {
+    writeln!(writer, "{};{};{}", interval.title, interval.created, interval.removed.as_deref().unwrap_or_default())
+}
+
+/// The timestamp as in the dump, `YYYY-MM-DDTHH:MM:SSZ`, given either like that or as a date
+/// `YYYY-MM-DD`, which means the start of the day. Anything else can't be compared with the
+/// timestamps in the dump.
+fn parse_timestamp(as_of: &str) -> Result<String> {
+    let timestamp = if as_of.len() == 10 { format!("{as_of}T00:00:00Z") } else { as_of.to_owned() };
+    let fields = [(0..4, 0..=9999), (5..7, 1..=12), (8..10, 1..=31), (11..13, 0..=23), (14..16, 0..=59), (17..19, 0..=59)];
+
+    let valid = timestamp.len() == 20
+        && timestamp.char_indices().all(|(index, character)| match index {
+            4 | 7 => character == '-',
+            10 => character == 'T',
+            13 | 16 => character == ':',
+            19 => character == 'Z',
+            _ => character.is_ascii_digit()
+        })
+        && fields.into_iter().all(|(range, valid_values)| timestamp[range].parse().is_ok_and(|value: u32| valid_values.contains(&value)));
+
+    if valid {
+        Ok(timestamp)
+    } else {
+        Err(Error::new(ErrorKind::InvalidInput, format!("{as_of} is no date like 2015-01-01 or timestamp like 2015-01-01T12:00:00Z")))
+    }
+}
+
+/// Whether something which existed from `start` until `end`, or until now without an end,
+/// existed at the time `as_of`.
+fn alive_at(start: &str, end: &str, as_of: &str) -> bool {
+    start <= as_of && (end.is_empty() || end > as_of)
+}
+
+/// The network as it was at the time `as_of`, built from the links which were added before and
+/// not yet removed at that time. The articles are taken from the page intervals next to the
+/// temporal edges, so articles without links at that time are part of the network as well. For
+/// files without page intervals only the articles with links are known. A date without a time
+/// means the start of the day.
+pub fn load_temporal_network(temporal_file_path: PathBuf, as_of: &str) -> Result<HashMap<String, Vec<String>>> {
+    let as_of = parse_timestamp(as_of)?;
+    let mut network: HashMap<String, Vec<String>> = HashMap::new();
+
+    match File::open(pages_file_path(&temporal_file_path)) {
+        Ok(pages_file) => {
+            for line in BufReader::new(pages_file).lines() {
+                let line = line?;
+                let mut parts = line.rsplitn(3, ';');
+
+                if let (Some(removed), Some(created), Some(title)) = (parts.next(), parts.next(), parts.next()) {
+                    if alive_at(created, removed, &as_of) {
+                        network.entry(title.to_owned()).or_default();
+                    }
+                }
+            }
+        },
+        Err(error) if error.kind() == ErrorKind::NotFound => println!("found no page intervals, only articles with links are part of the network"),
+        Err(error) => return Err(error)
+    }
+
+    let reader = BufReader::new(File::open(temporal_file_path)?);
+
+    for line in reader.lines() {
+        let line = line?;
+        let mut parts = line.rsplitn(4, ';');
+
+        let (Some(removed), Some(added), Some(target), Some(source)) = (parts.next(), parts.next(), parts.next(), parts.next()) else {
+            continue;
+        };
+
+        if alive_at(added, removed, &as_of) {
+            network.entry(source.to_owned()).or_default().push(target.to_owned());
+        }
+    }
+
+    Ok(network)
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::BTreeSet;
+
+    use std::io::ErrorKind;
+
+    use super::{alive_at, parse_timestamp, LinkHistory, PageInterval, TemporalEdge};
+
+    fn links(titles: &[&str]) -> BTreeSet<String> {
+        titles.iter().map(|title| title.to_string()).collect()
+    }
+
+    #[test]
+    fn test_link_history() {
+        let mut history = LinkHistory::new("Berlin".to_owned());
+
+        assert!(history.update(links(&["Germany", "Bonn"]), true, "2001").is_empty());
+        assert_eq!(history.update(links(&["Germany"]), true, "2002"), vec![TemporalEdge {
+            source: "Berlin".to_owned(), target: "Bonn".to_owned(), added: "2001".to_owned(), removed: Some("2002".to_owned())
+        }]);
+        assert!(history.update(links(&["Germany", "Bonn"]), true, "2003").is_empty());
+
+        let (mut edges, _) = history.finish();
+        edges.sort_by(|first, second| first.target.cmp(&second.target));
+
+        assert_eq!(edges, vec![
+            TemporalEdge { source: "Berlin".to_owned(), target: "Bonn".to_owned(), added: "2003".to_owned(), removed: None },
+            TemporalEdge { source: "Berlin".to_owned(), target: "Germany".to_owned(), added: "2001".to_owned(), removed: None }
+        ]);
+    }
+
+    #[test]
+    fn test_page_intervals() {
+        let mut history = LinkHistory::new("Bonn".to_owned());
+
+        history.update(links(&["Germany"]), true, "2001");
+        assert_eq!(history.update(BTreeSet::new(), false, "2002").len(), 1);
+        history.update(BTreeSet::new(), false, "2003");
+        history.update(BTreeSet::new(), true, "2004");
+
+        let (edges, intervals) = history.finish();
+
+        assert!(edges.is_empty());
+        assert_eq!(intervals, vec![
+            PageInterval { title: "Bonn".to_owned(), created: "2001".to_owned(), removed: Some("2002".to_owned()) },
+            PageInterval { title: "Bonn".to_owned(), created: "2004".to_owned(), removed: None }
+        ]);
+        assert!(alive_at("2001", "2002", "2001-06-01T00:00:00Z"));
+        assert!(!alive_at("2001", "2002", "2003-01-01T00:00:00Z"));
+        assert!(alive_at("2004", "", "2020-01-01T00:00:00Z"));
+    }
+
+    #[test]
+    fn test_parse_timestamp() {
+        assert_eq!(parse_timestamp("2020-01-01").unwrap(), "2020-01-01T00:00:00Z");
+        assert_eq!(parse_timestamp("2020-01-01T12:30:00Z").unwrap(), "2020-01-01T12:30:00Z");
+
+        for as_of in ["2020", "2020-1-1", "yesterday", "2020-13-01", "2020-01-01T25:00:00Z", "2020-01-01 12:30:00", "२०२०-01-01"] {
+            assert_eq!(parse_timestamp(as_of).unwrap_err().kind(), ErrorKind::InvalidInput, "{as_of}");
+        }
+    }
+}
@@ -0,0 +1,145 @@
+use std::{collections::{HashMap, HashSet}, fs::File, io::{BufRead, BufReader, BufWriter, Error, Result, Write}, path::PathBuf};
+
+use aho_corasick::{AhoCorasick, MatchKind};
+
+use crate::{analyze::load_network, common::{find_entries, process_dump, read_from}, network_generator::{wiki_text::unlinked_text, wiki_xml_dump::WikiPage}};
+
+/// Shorter titles are too often ordinary words to count as mentions.
+const MIN_TITLE_LENGTH: usize = 4;
+
+/// Finds titles of articles and redirects in the text of a page outside of links.
+struct MentionMatcher {
+    automaton: AhoCorasick,
+    targets: Vec<String>,
+    articles: HashSet<String>
+}
+
+impl MentionMatcher {
+    fn new(network_file_path: PathBuf, redirects_file_path: Option<PathBuf>) -> Result<MentionMatcher> {
+        let articles = load_network(network_file_path)?.into_keys().collect::<HashSet<String>>();
+
+        let mut patterns = Vec::new();
+        let mut targets = Vec::new();
+
+        for article in &articles {
+            patterns.push(article.to_owned());
+            targets.push(article.to_owned());
+        }
+
+        if let Some(redirects_file_path) = redirects_file_path {
+            for (redirect, target) in read_redirects(redirects_file_path)? {
+                if articles.contains(&target) {
+                    patterns.push(redirect);
+                    targets.push(target);
+                }
+            }
+        }
+
+        let (patterns, targets): (Vec<String>, Vec<String>) = patterns.into_iter()
+            .zip(targets)
+            .filter(|(pattern, _)| pattern.chars().count() >= MIN_TITLE_LENGTH)
+            .unzip();
+
+        println!("building automaton for {} titles", patterns.len());
+
+        let automaton = AhoCorasick::builder()
+            .match_kind(MatchKind::LeftmostLongest)
+            .build(&patterns)
+            .map_err(Error::other)?;
+
+        Ok(MentionMatcher { automaton, targets, articles })
+    }
+
+    /// Number of unlinked mentions of every other article in `page`.
+    fn mentions(&self, page: &WikiPage) -> HashMap<&String, usize> {
+        let mut mentions = HashMap::new();
+
+        let Some(text) = page.text.as_ref() else {
+            return mentions;
+        };
+
+        let text = unlinked_text(text);
+
+        for found in self.automaton.find_iter(&text) {
+            let before = text[..found.start()].chars().next_back();
+            let after = text[found.end()..].chars().next();
+
+            if before.is_some_and(char::is_alphanumeric) || after.is_some_and(char::is_alphanumeric) {
+                continue;
+            }
+
+            let target = &self.targets[found.pattern().as_usize()];
+
+            if *target != page.title {
+                *mentions.entry(target).or_insert(0) += 1;
+            }
+        }
+
+        mentions
+    }
+}
+
+/// Prints the unlinked mentions of other articles in the article with the given title.
+pub fn unlinked_mentions(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, title: &String, network_file_path: PathBuf, redirects_file_path: Option<PathBuf>) -> Result<()> {
+    let matcher = MentionMatcher::new(network_file_path, redirects_file_path)?;
+
+    for entry in find_entries(xml_dump_index_path, title)? {
+        for page in read_from(xml_dump_path, entry.start)? {
+            if page.title == entry.title {
+                let mut mentions = matcher.mentions(&page).into_iter().collect::<Vec<(&String, usize)>>();
+                mentions.sort_by(|(a_title, a), (b_title, b)| b.cmp(a).then_with(|| a_title.cmp(b_title)));
+
+                for (target, count) in mentions {
+                    println!("{target}\t{count}");
+                }
+
+                return Ok(());
+            }
+        }
+    }
+
+    println!("No article with title {title} found!");
+
+    Ok(())
+}
+
+/// Writes `source;target;count` for the unlinked mentions in every article of the dump.
+pub fn all_unlinked_mentions(xml_dump_path: &PathBuf, xml_dump_index_path: &PathBuf, output_file_path: PathBuf, network_file_path: PathBuf, redirects_file_path: Option<PathBuf>) -> Result<()> {
+    let number_of_threads = 4;
+    let matcher = MentionMatcher::new(network_file_path, redirects_file_path)?;
+
+    let process = |page: WikiPage| {
+        if !matcher.articles.contains(&page.title) {
+            return None;
+        }
+
+        let mentions = matcher.mentions(&page).into_iter()
+            .map(|(target, count)| (target.to_owned(), count))
+            .collect::<Vec<(String, usize)>>();
+
+        Some((page.title, mentions))
+    };
+
+    process_dump(xml_dump_path, xml_dump_index_path, number_of_threads, process, |results| {
+        let mut writer = BufWriter::new(File::create(output_file_path)?);
+
+        for (source, mentions) in results {
+            for (target, count) in mentions {
+                writeln!(writer, "{source};{target};{count}")?;
+            }
+        }
+
+        writer.flush()
+    })
+}
+
+fn read_redirects(redirects_file_path: PathBuf) -> Result<Vec<(String, String)>> {
+    let reader = BufReader::new(File::open(redirects_file_path)?);
+
+    reader.lines()
+        .filter_map(|line| match line {
+            Ok(line) => line.split_once(';').map(|(redirect, target)| Ok((redirect.to_owned(), target.to_owned()))),
+            Err(error) => Some(Err(error))
+        })
+        .collect()
+}
@@ -0,0 +1,292 @@
+use std::{collections::{HashMap, HashSet}, fs::{rename, File}, io::{BufRead, BufReader, BufWriter, Result, Write}, path::PathBuf};
+
+use crate::{common::{read_from, read_namespaces}, network::select_namespaces, network_generator::{canonicalize_link, wiki_text::{is_redirect, linked_articles, parse_text, redirects_to}, wiki_xml_dump::WikiPage}};
+
+/// What became of a page since the network was generated.
+#[derive(Debug, PartialEq)]
+enum PageChange {
+    Article(Vec<String>),
+    Redirect(String),
+    Deleted
+}
+
+/// Patches a network and its redirects with the pages of adds-changes dumps, which only contain
+/// pages edited since the previous dump. Only the latest revision of a page counts, later dumps
+/// override earlier ones. Deletions are not part of these dumps and are read from a file with one
+/// title per line instead.
+pub fn update_network(network_file_path: PathBuf, redirects_file_path: PathBuf, changes_file_paths: Vec<PathBuf>, deleted_file_path: Option<PathBuf>, namespaces: Vec<String>) -> Result<()> {
+    let mut changes = HashMap::new();
+
+    for changes_file_path in changes_file_paths {
+        println!("reading changed pages from {}", changes_file_path.display());
+        read_changes(&changes_file_path, &namespaces, &mut changes)?;
+    }
+
+    if let Some(deleted_file_path) = deleted_file_path {
+        for title in BufReader::new(File::open(deleted_file_path)?).lines() {
+            let title = title?;
+
+            if !title.trim().is_empty() {
+                changes.insert(title.trim().to_owned(), PageChange::Deleted);
+            }
+        }
+    }
+
+    println!("updating the network with {} changed pages", changes.len());
+    update_adjacency(&network_file_path, &changes)?;
+
+    println!("updating the redirects");
+    update_redirects(&redirects_file_path, &changes)
+}
+
+fn read_changes(changes_file_path: &PathBuf, namespaces: &[String], changes: &mut HashMap<String, PageChange>) -> Result<()> {
+    let selected_namespaces = select_namespaces(&read_namespaces(changes_file_path)?, namespaces)?;
+
+    for page in read_from(changes_file_path, 0)? {
+        if !selected_namespaces.contains(&page.namespace_id) {
+            continue;
+        }
+
+        if let Some(change) = page_change(&page) {
+            changes.insert(page.title, change);
+        }
+    }
+
+    Ok(())
+}
+
+/// The links or the redirect target of the latest revision of a page, none for pages without text.
+fn page_change(page: &WikiPage) -> Option<PageChange> {
+    let links = parse_text(page)?;
+
+    if is_redirect(&links) {
+        redirects_to(&links).and_then(canonicalize_link).map(PageChange::Redirect)
+    } else {
+        Some(PageChange::Article(linked_articles(&links).iter()
+            .filter_map(|link| canonicalize_link(link))
+            .collect()))
+    }
+}
+
+/// Writes the patched file next to the original and only replaces it once it is complete, so an
+/// interrupted update leaves the original file as it was.
+fn patch_file<P>(file_path: &PathBuf, patch: P) -> Result<()>
+    where P: FnOnce(BufReader<File>, &mut BufWriter<File>) -> Result<()> {
+    let temporary_file_path = file_path.with_extension("temp");
+    let reader = BufReader::new(File::open(file_path)?);
+    let mut writer = BufWriter::new(File::create(&temporary_file_path)?);
+
+    patch(reader, &mut writer)?;
+    writer.flush()?;
+    drop(writer);
+
+    rename(temporary_file_path, file_path)
+}
+
+fn update_adjacency(network_file_path: &PathBuf, changes: &HashMap<String, PageChange>) -> Result<()> {
+    patch_file(network_file_path, |reader, writer| patch_adjacency(reader, writer, changes))
+}
+
+/// Replaces the links of changed articles, drops articles which were deleted or turned into
+/// redirects and appends new articles.
+fn patch_adjacency<R: BufRead, W: Write>(reader: R, writer: &mut W, changes: &HashMap<String, PageChange>) -> Result<()> {
+    for line in reader.lines() {
+        let line = line?;
+        let title = line.split(';').next().unwrap_or_default();
+
+        if !changes.contains_key(title) {
+            writer.write_all(line.as_bytes())?;
+            writer.write_all(b"\n")?;
+        }
+    }
+
+    let mut articles = changes.iter()
+        .filter_map(|(title, change)| match change {
+            PageChange::Article(links) => Some((title, links)),
+            _ => None
+        })
+        .collect::<Vec<(&String, &Vec<String>)>>();
+    articles.sort();
+
+    for (title, links) in articles {
+        writer.write_all(title.as_bytes())?;
+
+        for link in links {
+            writer.write_all(b"; ")?;
+            writer.write_all(link.as_bytes())?;
+        }
+
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+fn update_redirects(redirects_file_path: &PathBuf, changes: &HashMap<String, PageChange>) -> Result<()> {
+    patch_file(redirects_file_path, |reader, writer| patch_redirects(reader, writer, changes))
+}
+
+/// Replaces changed redirects and resolves the chains again for every redirect which leads
+/// through a changed page, all other redirects are kept as they are.
+fn patch_redirects<R: BufRead, W: Write>(reader: R, writer: &mut W, changes: &HashMap<String, PageChange>) -> Result<()> {
+    let mut redirects = Vec::new();
+
+    for line in reader.lines() {
+        let line = line?;
+
+        if let Some((link, target)) = line.split_once(';') {
+            if !changes.contains_key(link) {
+                redirects.push((link.to_owned(), target.to_owned()));
+            }
+        }
+    }
+
+    let mut changed_redirects = changes.iter()
+        .filter_map(|(title, change)| match change {
+            PageChange::Redirect(target) => Some((title.to_owned(), target.to_owned())),
+            _ => None
+        })
+        .collect::<Vec<(String, String)>>();
+    changed_redirects.sort();
+    redirects.extend(changed_redirects);
+
+    let affected = affected_redirects(&redirects, changes);
+    let targets = redirects.iter()
+        .map(|(link, target)| (link.as_str(), target.as_str()))
+        .collect::<HashMap<&str, &str>>();
+
+    let resolved = affected.iter()
+        .map(|link| (link.to_owned(), resolve_chain(&targets, link)))
+        .collect::<HashMap<String, Option<String>>>();
+
+    for (link, target) in &redirects {
+        let target = match resolved.get(link) {
+            Some(Some(target)) => target,
+            Some(None) => {
+                println!("found cyclic redirects for {}", link);
+                continue;
+            },
+            None => target
+        };
+
+        writer.write_all(link.as_bytes())?;
+        writer.write_all(b";")?;
+        writer.write_all(target.as_bytes())?;
+        writer.write_all(b"\n")?;
+    }
+
+    Ok(())
+}
+
+/// The redirects which were changed or lead to a changed page, directly or through other
+/// redirects.
+fn affected_redirects(redirects: &[(String, String)], changes: &HashMap<String, PageChange>) -> HashSet<String> {
+    let mut sources: HashMap<&str, Vec<&str>> = HashMap::new();
+
+    for (link, target) in redirects {
+        sources.entry(target.as_str()).or_default().push(link.as_str());
+    }
+
+    let mut affected = HashSet::new();
+    let mut stack = changes.keys().map(|title| title.as_str()).collect::<Vec<&str>>();
+
+    while let Some(title) = stack.pop() {
+        if matches!(changes.get(title), Some(PageChange::Redirect(_))) {
+            affected.insert(title.to_owned());
+        }
+
+        for source in sources.get(title).into_iter().flatten() {
+            if affected.insert(source.to_string()) {
+                stack.push(source);
+            }
+        }
+    }
+
+    affected
+}
+
+/// The page at the end of the chain of redirects starting at `link`, none for cycles.
+fn resolve_chain(targets: &HashMap<&str, &str>, link: &str) -> Option<String> {
+    let mut visited = HashSet::from([link]);
+    let mut current = targets.get(link)?;
+
+    while let Some(next) = targets.get(current) {
+        if !visited.insert(current) {
+            return None;
+        }
+
+        current = next;
+    }
+
+    Some(current.to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use std::collections::HashMap;
+
+    use crate::network_generator::wiki_xml_dump::WikiPage;
+
+    use super::{affected_redirects, page_change, patch_adjacency, patch_redirects, resolve_chain, PageChange};
+
+    fn changes() -> HashMap<String, PageChange> {
+        HashMap::from([
+            ("Berlin".to_owned(), PageChange::Article(vec!["Germany".to_owned(), "Potsdam".to_owned()])),
+            ("Old capital".to_owned(), PageChange::Redirect("Bonn".to_owned())),
+            ("Gone".to_owned(), PageChange::Deleted),
+            ("Potsdam".to_owned(), PageChange::Article(vec!["Berlin".to_owned()]))
+        ])
+    }
+
+    #[test]
+    fn test_page_change() {
+        let page = |text: Option<&str>| WikiPage { title: "Berlin".to_owned(), text: text.map(|text| text.to_owned()), namespace_id: 0 };
+
+        assert_eq!(page_change(&page(Some("[[Germany]] and [[potsdam|Potsdam]]"))), Some(PageChange::Article(vec!["Germany".to_owned(), "Potsdam".to_owned()])));
+        assert_eq!(page_change(&page(Some("#REDIRECT [[bonn]]"))), Some(PageChange::Redirect("Bonn".to_owned())));
+        assert_eq!(page_change(&page(None)), None);
+    }
+
+    #[test]
+    fn test_patch_adjacency() {
+        let network = "Berlin; Germany\nBonn; Berlin\nOld capital; Bonn\nGone; Berlin\n";
+        let mut output = Vec::new();
+
+        patch_adjacency(network.as_bytes(), &mut output, &changes()).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Bonn; Berlin\nBerlin; Germany; Potsdam\nPotsdam; Berlin\n");
+    }
+
+    #[test]
+    fn test_patch_redirects() {
+        let redirects = "Deutschland;Germany\nCapital;Old capital\nGone;Berlin\n";
+        let mut output = Vec::new();
+
+        patch_redirects(redirects.as_bytes(), &mut output, &changes()).unwrap();
+
+        assert_eq!(String::from_utf8(output).unwrap(), "Deutschland;Germany\nCapital;Bonn\nOld capital;Bonn\n");
+    }
+
+    #[test]
+    fn test_affected_redirects() {
+        let redirects = vec![
+            ("USA".to_owned(), "America".to_owned()),
+            ("America".to_owned(), "United States".to_owned()),
+            ("Deutschland".to_owned(), "Germany".to_owned())
+        ];
+        let changes = HashMap::from([("United States".to_owned(), PageChange::Article(Vec::new()))]);
+
+        let mut affected = affected_redirects(&redirects, &changes).into_iter().collect::<Vec<String>>();
+        affected.sort();
+
+        assert_eq!(affected, vec!["America", "USA"]);
+
+        let targets = redirects.iter()
+            .map(|(link, target)| (link.as_str(), target.as_str()))
+            .chain([("Loop", "Cycle"), ("Cycle", "Loop")])
+            .collect::<HashMap<&str, &str>>();
+
+        assert_eq!(resolve_chain(&targets, "USA"), Some("United States".to_owned()));
+        assert_eq!(resolve_chain(&targets, "Loop"), None);
+    }
+}
@@ -1,16 +1,30 @@
 import { useState } from 'react'
-import { Statistics, fetchStatistics } from './api'
+import { NetworkDiff, Statistics, fetchDiff, fetchStatistics } from './api'
 import { Summary } from './components/summary';
-import { DegreeChart } from './components/degreeChart';
+import { DegreeDistributions } from './components/degreeDistributions';
+import { Ranking } from './components/ranking';
+import { PowerLawTable } from './components/powerLawTable';
+import { Changes } from './components/changes';
+import { pageUrl } from './urls';
 
 function App() {
 
   const [statistics, setStatistics] = useState<Statistics | null>(null)
 
+  const [diff, setDiff] = useState<NetworkDiff | null | undefined>(undefined)
+
   if (statistics === null) {
     fetchStatistics().then(setStatistics);
   }
 
+  if (diff === undefined) {
+    fetchDiff().then(setDiff);
+  }
+
+  const pageRank = statistics?.["page-rank"];
+  const kCore = statistics?.["k-core"];
+  const powerLaw = statistics?.["power-law"];
+
   return (
     <>
       <h1>Wikilytics</h1>
@@ -21,26 +35,107 @@ function App() {
         : 
         <div>
           <div>
-            <Summary statistics={statistics} />
-          </div>
-          <div>
-            <details>
-              <summary>Outdegree distribution</summary>
-                <figure>
-                  <figcaption>Outdegree distribution</figcaption>
-                  <DegreeChart data={statistics.outDegreeDistribution.map((numberOfNodes, degree) => [degree, numberOfNodes])} degreeLabel='Outdegree'/>
-                </figure>
-            </details>
-          </div>
-          <div>
-            <details>
-              <summary>Indegree distribution</summary>
-                <figure>
-                  <figcaption>Indegree distribution</figcaption>
-                  <DegreeChart data={statistics.inDegreeDistribution.map((numberOfNodes, degree) => [degree, numberOfNodes])} degreeLabel='Indegree'/>
-                </figure>
-            </details>
+            <Summary mainPage={statistics.mainPage} summary={statistics.summary} degrees={statistics.degrees} />
           </div>
+          { statistics.degrees !== undefined && <>
+            <DegreeDistributions degreeLabel='Outdegree' distribution={statistics.degrees.outDegreeDistribution} ccdf={statistics.degrees.outDegreeCcdf} logBins={statistics.degrees.outDegreeLogBins} fit={powerLaw?.outDegree}/>
+            <DegreeDistributions degreeLabel='Indegree' distribution={statistics.degrees.inDegreeDistribution} ccdf={statistics.degrees.inDegreeCcdf} logBins={statistics.degrees.inDegreeLogBins} fit={powerLaw?.inDegree}/>
+          </> }
+          { diff !== null && diff !== undefined &&
+            <div>
+              <details>
+                <summary>What changed since the last snapshot</summary>
+                <Changes mainPage={statistics.mainPage} diff={diff}/>
+              </details>
+            </div>
+          }
+          { powerLaw !== undefined &&
+            <div>
+              <details>
+                <summary>Power law fits</summary>
+                <PowerLawTable outDegree={powerLaw.outDegree} inDegree={powerLaw.inDegree}/>
+              </details>
+            </div>
+          }
+          { pageRank !== undefined &&
+            <div>
+              <details>
+                <summary>PageRank</summary>
+                <Ranking mainPage={statistics.mainPage} nodes={pageRank} scoreLabel='PageRank'/>
+              </details>
+            </div>
+          }
+          { statistics.hits !== undefined &&
+            <div>
+              <details>
+                <summary>Hubs and authorities</summary>
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.hits.hubs} scoreLabel='Hub score'/>
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.hits.authorities} scoreLabel='Authority score'/>
+              </details>
+            </div>
+          }
+          { statistics.betweenness !== undefined &&
+            <div>
+              <details>
+                <summary>Betweenness</summary>
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.betweenness} scoreLabel='Betweenness'/>
+              </details>
+            </div>
+          }
+          { kCore !== undefined &&
+            <div>
+              <details>
+                <summary>Maximum core ({kCore.maxCore}-core, {kCore.nodesOfMaxCore.length} articles)</summary>
+                <Ranking mainPage={statistics.mainPage} nodes={kCore.topCoreNumber} scoreLabel='Core number'/>
+                {kCore.nodesOfMaxCore.map(pageName => <a href={pageUrl(statistics.mainPage, pageName).toString()}>{pageName} </a>)}
+              </details>
+            </div>
+          }
+          { statistics.citations !== undefined &&
+            <div>
+              <details>
+                <summary>Citations ({statistics.citations.meanCitations.toFixed(1)} per article, {statistics.citations.numberOfArticlesWithoutCitations} articles without citations)</summary>
+                { statistics.citations.inDegreeCorrelation !== null &&
+                  <p>Rank correlation with the indegree: {statistics.citations.inDegreeCorrelation.toFixed(3)}</p>
+                }
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.citations.topCitations} scoreLabel='Citations'/>
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.citations.uncitedByInDegree} scoreLabel='Indegree without citations'/>
+              </details>
+            </div>
+          }
+          { statistics.disambiguation !== undefined &&
+            <div>
+              <details>
+                <summary>Disambiguation ({statistics.disambiguation.numberOfLinksToDisambiguationPages} links from {statistics.disambiguation.numberOfArticlesLinkingToDisambiguationPages} articles to {statistics.disambiguation.numberOfDisambiguationPages} disambiguation pages)</summary>
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.disambiguation.mostLinkedDisambiguationPages} scoreLabel='Incoming links'/>
+                <Ranking mainPage={statistics.mainPage} nodes={statistics.disambiguation.articlesWithMostDisambiguationLinks} scoreLabel='Links to disambiguation pages'/>
+              </details>
+            </div>
+          }
+          { statistics.segments !== undefined &&
+            <div>
+              <details>
+                <summary>Degrees by kind of article</summary>
+                <table>
+                  <thead>
+                    <tr><th>Kind</th><th>Articles</th><th>Mean outdegree</th><th>Mean indegree</th><th>Mean length</th><th>Links per 1000 bytes</th></tr>
+                  </thead>
+                  <tbody>
+                    { Object.entries(statistics.segments).map(([segment, segmentStatistics]) =>
+                      <tr key={segment}>
+                        <td>{segment}</td>
+                        <td>{segmentStatistics.numberOfArticles}</td>
+                        <td>{segmentStatistics.meanOutDegree.toFixed(1)}</td>
+                        <td>{segmentStatistics.meanInDegree.toFixed(1)}</td>
+                        <td>{segmentStatistics.meanLength.toFixed(0)}</td>
+                        <td>{segmentStatistics.linkDensity.toFixed(2)}</td>
+                      </tr>
+                    )}
+                  </tbody>
+                </table>
+              </details>
+            </div>
+          }
         </div>
       }
       
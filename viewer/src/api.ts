@@ -1,16 +1,151 @@
-export interface Statistics {
-    mainPage: string;
+export interface SummaryStatistics {
     numberOfNodes: number;
     numberOfEdges: number;
+}
+
+export interface DegreeStatistics {
     nodesOfMaxOutDegree: [string];
     maxOutDegree: number;
     nodesOfMaxInDegree: [string];
     maxInDegree: number;
-    outDegreeDistribution: [number];
-    inDegreeDistribution: [number];
+    topOutDegree: ScoredNode[];
+    topInDegree: ScoredNode[];
+    outDegreeDistribution: [number, number][];
+    inDegreeDistribution: [number, number][];
+    outDegreeCcdf?: [number, number][];
+    inDegreeCcdf?: [number, number][];
+    outDegreeLogBins?: [number, number][];
+    inDegreeLogBins?: [number, number][];
+}
+
+export interface ScoredNode {
+    title: string;
+    score: number;
+}
+
+export interface HitsStatistics {
+    hubs: ScoredNode[];
+    authorities: ScoredNode[];
+}
+
+export interface KCoreStatistics {
+    maxCore: number;
+    nodesOfMaxCore: string[];
+    topCoreNumber: ScoredNode[];
+}
+
+export interface LognormalComparison {
+    mu: number;
+    sigma: number;
+    logLikelihoodRatio: number;
+    normalizedRatio: number;
+    pValue: number;
+}
+
+export interface PowerLawFit {
+    xMin: number;
+    alpha: number;
+    alphaError: number;
+    ksStatistic: number;
+    pValue: number | null;
+    numberOfBootstraps: number;
+    numberOfObservations: number;
+    numberOfTailObservations: number;
+    normalization: number;
+    lognormal: LognormalComparison;
+}
+
+export interface PowerLawStatistics {
+    outDegree: PowerLawFit | null;
+    inDegree: PowerLawFit | null;
+}
+
+export interface CitationStatistics {
+    numberOfArticles: number;
+    numberOfCitations: number;
+    meanCitations: number;
+    medianCitations: number;
+    numberOfArticlesWithoutCitations: number;
+    citationDistribution: [number, number][];
+    topCitations: ScoredNode[];
+    uncitedByInDegree: ScoredNode[];
+    inDegreeCorrelation: number | null;
+}
+
+export interface DisambiguationStatistics {
+    numberOfDisambiguationPages: number;
+    numberOfLinksToDisambiguationPages: number;
+    numberOfArticlesLinkingToDisambiguationPages: number;
+    mostLinkedDisambiguationPages: ScoredNode[];
+    articlesWithMostDisambiguationLinks: ScoredNode[];
+}
+
+export interface SegmentStatistics {
+    numberOfArticles: number;
+    meanOutDegree: number;
+    medianOutDegree: number;
+    meanInDegree: number;
+    medianInDegree: number;
+    meanLength: number;
+    linkDensity: number;
+    meanTemplates: number;
+}
+
+export interface Statistics {
+    mainPage: string;
+    summary?: SummaryStatistics;
+    degrees?: DegreeStatistics;
+    "page-rank"?: ScoredNode[];
+    hits?: HitsStatistics;
+    betweenness?: ScoredNode[];
+    "k-core"?: KCoreStatistics;
+    "power-law"?: PowerLawStatistics;
+    citations?: CitationStatistics;
+    disambiguation?: DisambiguationStatistics;
+    segments?: Record<string, SegmentStatistics>;
 }
 
 export function fetchStatistics(): Promise<Statistics> {
     return fetch("statistics.json")
         .then(response => response.json());
-}
\ No newline at end of file
+}
+
+export interface ChurnStatistics {
+    numberOfArticlesBefore: number;
+    numberOfArticlesAfter: number;
+    numberOfAddedArticles: number;
+    numberOfRemovedArticles: number;
+    numberOfRenamedArticles: number;
+    numberOfChangedArticles: number;
+    numberOfLinksBefore: number;
+    numberOfLinksAfter: number;
+    numberOfAddedLinks: number;
+    numberOfRemovedLinks: number;
+    linkChurn: number;
+}
+
+export interface Rename {
+    from: string;
+    to: string;
+}
+
+export interface ArticleDiff {
+    title: string;
+    addedLinks: string[];
+    removedLinks: string[];
+}
+
+export interface NetworkDiff {
+    statistics: ChurnStatistics;
+    addedArticles: string[];
+    removedArticles: string[];
+    renamedArticles: Rename[];
+    mostChangedArticles: ScoredNode[];
+    changedArticles: ArticleDiff[];
+}
+
+export function fetchDiff(): Promise<NetworkDiff | null> {
+    return fetch("diff.json")
+        .then(response => response.ok ? response.json() : null)
+        .catch(() => null);
+}
@@ -0,0 +1,39 @@
+import { NetworkDiff } from '../api'
+import { pageUrl } from '../urls';
+import { Ranking } from './ranking';
+
+export function Changes({ mainPage, diff }: { mainPage: string; diff: NetworkDiff }) {
+    const statistics = diff.statistics;
+
+    return <>
+        <table>
+            <tbody>
+                <tr>
+                    <td>Articles</td><td>{statistics.numberOfArticlesBefore} → {statistics.numberOfArticlesAfter}</td>
+                </tr>
+                <tr>
+                    <td>Links</td><td>{statistics.numberOfLinksBefore} → {statistics.numberOfLinksAfter}</td>
+                </tr>
+                <tr>
+                    <td>Added, removed and renamed articles</td>
+                    <td>{statistics.numberOfAddedArticles} / {statistics.numberOfRemovedArticles} / {statistics.numberOfRenamedArticles}</td>
+                </tr>
+                <tr>
+                    <td>Added and removed links</td>
+                    <td>{statistics.numberOfAddedLinks} / {statistics.numberOfRemovedLinks} in {statistics.numberOfChangedArticles} articles</td>
+                </tr>
+                <tr>
+                    <td>Link churn</td><td>{(statistics.linkChurn * 100).toFixed(2)} %</td>
+                </tr>
+            </tbody>
+        </table>
+        <Ranking mainPage={mainPage} nodes={diff.mostChangedArticles} scoreLabel='Added and removed links'/>
+        { diff.renamedArticles.length > 0 &&
+            <ul>
+                {diff.renamedArticles.map(rename => <li key={rename.from}>
+                    {rename.from} → <a href={pageUrl(mainPage, rename.to).toString()}>{rename.to}</a>
+                </li>)}
+            </ul>
+        }
+    </>
+}
@@ -1,7 +1,8 @@
 import * as d3 from "d3"
 import { useEffect, useRef } from "react"
+import { PowerLawFit } from "../api"
 
-export function DegreeChart({ data, degreeLabel }: { data: [number, number][], degreeLabel: string }) {
+export function DegreeChart({ data, degreeLabel, valueLabel = "Number of Nodes", fit }: { data: [number, number][], degreeLabel: string, valueLabel?: string, fit?: PowerLawFit | null }) {
     const svgRef = useRef<SVGSVGElement>(null)
 
     useEffect(() => {
@@ -12,14 +13,17 @@ export function DegreeChart({ data, degreeLabel }: { data: [number, number][], d
         const marginBottom = 50;
         const marginLeft = 50;
 
-        const xValues = data.map(entry => entry[0])
-        const yValues = data.map(entry => entry[1])
+        const positiveData = data.filter(xy => xy[0] > 0 && xy[1] > 0)
+        const xValues = positiveData.map(entry => entry[0])
+        const yValues = positiveData.map(entry => entry[1])
     
+        const xMin = xValues.reduce((min, x) => Math.min(min, x), 1)
         const xMax = xValues.reduce((max, x) => Math.max(max, x), Number.MIN_VALUE)
+        const yMin = yValues.reduce((min, y) => Math.min(min, y), 1)
         const yMax = yValues.reduce((max, y) => Math.max(max, y), Number.MIN_VALUE)
 
-        const xScale = d3.scaleLog([1, xMax], [marginLeft, width - marginRight])
-        const yScale = d3.scaleLog([1, yMax], [height - marginBottom, marginTop])
+        const xScale = d3.scaleLog([xMin, xMax], [marginLeft, width - marginRight])
+        const yScale = d3.scaleLog([yMin, yMax], [height - marginBottom, marginTop])
 
         if (svgRef.current !== null) {
             svgRef.current.innerHTML = ""
@@ -33,12 +37,29 @@ export function DegreeChart({ data, degreeLabel }: { data: [number, number][], d
         chartSvg.append("g")
             .attr("fill", "steelblue")
             .selectAll()
-            .data(data.filter(xy => xy[1] !== 0))
+            .data(positiveData)
             .join("circle")
                 .attr("cx", xy => xScale(xy[0]))
                 .attr("cy", xy => yScale(xy[1]))
                 .attr("r", radius)
 
+        if (fit) {
+            const numberOfPoints = 100
+            const logStart = Math.log(fit.xMin)
+            const logStep = (Math.log(xMax) - logStart) / numberOfPoints
+            const fittedData = d3.range(numberOfPoints + 1)
+                .map(step => Math.exp(logStart + step * logStep))
+                .map((degree): [number, number] => [degree, fit.numberOfTailObservations * Math.pow(degree, -fit.alpha) / fit.normalization])
+                .filter(xy => xy[1] >= yMin)
+
+            chartSvg.append("path")
+                .datum(fittedData)
+                .attr("fill", "none")
+                .attr("stroke", "darkorange")
+                .attr("stroke-width", 1.5)
+                .attr("d", d3.line(xy => xScale(xy[0]), xy => yScale(xy[1])))
+        }
+
         chartSvg.append("g")
             .attr("transform", `translate(0, ${height - marginBottom})`)
             .call(d3.axisBottom(xScale))
@@ -55,9 +76,9 @@ export function DegreeChart({ data, degreeLabel }: { data: [number, number][], d
                 .attr("x", 40)
                 .attr("y", 40)
                 .attr("fill", "currentColor")
-                .text("Number of Nodes"))
+                .text(valueLabel))
 
-    }, [data])
+    }, [data, degreeLabel, valueLabel, fit])
 
     return <>
         <svg ref={svgRef}></svg>
@@ -0,0 +1,28 @@
+import { PowerLawFit } from '../api'
+import { DegreeChart } from './degreeChart'
+
+export function DegreeDistributions({ degreeLabel, distribution, ccdf, logBins, fit }: { degreeLabel: string; distribution: [number, number][]; ccdf?: [number, number][]; logBins?: [number, number][]; fit?: PowerLawFit | null }) {
+    return <>
+        <div>
+            <details>
+                <summary>{degreeLabel} distribution</summary>
+                <figure>
+                    <figcaption>{degreeLabel} distribution</figcaption>
+                    <DegreeChart data={distribution} degreeLabel={degreeLabel} fit={fit}/>
+                </figure>
+                { logBins !== undefined &&
+                    <figure>
+                        <figcaption>{degreeLabel} distribution in logarithmic bins</figcaption>
+                        <DegreeChart data={logBins} degreeLabel={degreeLabel} valueLabel='Nodes per degree' fit={fit}/>
+                    </figure>
+                }
+                { ccdf !== undefined &&
+                    <figure>
+                        <figcaption>Complementary cumulative {degreeLabel.toLowerCase()} distribution</figcaption>
+                        <DegreeChart data={ccdf} degreeLabel={degreeLabel} valueLabel='Fraction of nodes with at least this degree'/>
+                    </figure>
+                }
+            </details>
+        </div>
+    </>
+}
@@ -0,0 +1,38 @@
+import { PowerLawFit } from '../api'
+
+function formatOptional(value: number | null | undefined): string {
+    return value === null || value === undefined ? "-" : value.toPrecision(3)
+}
+
+export function PowerLawTable({ outDegree, inDegree }: { outDegree: PowerLawFit | null; inDegree: PowerLawFit | null }) {
+    const fits: [string, PowerLawFit | null][] = [["Outdegree", outDegree], ["Indegree", inDegree]]
+
+    return <>
+        <table>
+            <thead>
+                <tr>
+                    <th></th>
+                    <th>x<sub>min</sub></th>
+                    <th>α</th>
+                    <th>KS distance</th>
+                    <th>p-value</th>
+                    <th>Articles in tail</th>
+                    <th>Log-likelihood ratio to lognormal</th>
+                    <th>p-value of ratio</th>
+                </tr>
+            </thead>
+            <tbody>
+                {fits.map(([label, fit]) => <tr key={label}>
+                    <td>{label}</td>
+                    <td>{fit?.xMin ?? "-"}</td>
+                    <td>{fit === null ? "-" : `${fit.alpha.toPrecision(3)} ± ${fit.alphaError.toPrecision(2)}`}</td>
+                    <td>{formatOptional(fit?.ksStatistic)}</td>
+                    <td>{formatOptional(fit?.pValue)}</td>
+                    <td>{fit?.numberOfTailObservations ?? "-"}</td>
+                    <td>{formatOptional(fit?.lognormal.logLikelihoodRatio)}</td>
+                    <td>{formatOptional(fit?.lognormal.pValue)}</td>
+                </tr>)}
+            </tbody>
+        </table>
+    </>
+}
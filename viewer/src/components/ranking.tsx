@@ -0,0 +1,21 @@
+import { ScoredNode } from '../api'
+import { pageUrl } from '../urls';
+
+export function Ranking({ mainPage, nodes, scoreLabel }: { mainPage: string; nodes: ScoredNode[]; scoreLabel: string }) {
+    return <>
+        <table>
+            <thead>
+                <tr>
+                    <th>Rank</th><th>Article</th><th>{scoreLabel}</th>
+                </tr>
+            </thead>
+            <tbody>
+                {nodes.map((node, index) => <tr key={node.title}>
+                    <td>{index + 1}</td>
+                    <td><a href={pageUrl(mainPage, node.title).toString()}>{node.title}</a></td>
+                    <td>{Number.isInteger(node.score) ? node.score : node.score.toPrecision(4)}</td>
+                </tr>)}
+            </tbody>
+        </table>
+    </>
+}
@@ -1,29 +1,41 @@
-import { Statistics } from '../api'
+import { DegreeStatistics, ScoredNode, SummaryStatistics } from '../api'
 import { pageUrl } from '../urls';
 
-export function Summary({ statistics }: { statistics: Statistics;}) {
+function RankedList({ mainPage, nodes }: { mainPage: string; nodes: ScoredNode[] }) {
+    return <ol>
+        {nodes.map(node => <li key={node.title}>
+            <a href={pageUrl(mainPage, node.title).toString()}>{node.title}</a> ({node.score})
+        </li>)}
+    </ol>
+}
+
+export function Summary({ mainPage, summary, degrees }: { mainPage: string; summary?: SummaryStatistics; degrees?: DegreeStatistics }) {
     return <>
         <table>
             <tbody>
-                <tr>
-                    <td>Number of articles</td><td>{statistics.numberOfNodes}</td>
-                </tr>
-                <tr>
-                    <td>Number of links</td><td>{statistics.numberOfEdges}</td>
-                </tr>
-                <tr>
-                    <td>Articles with the most links</td><td>{statistics.nodesOfMaxOutDegree.map(pageName => <a href={pageUrl(statistics.mainPage, pageName).toString()}>{pageName}</a>)}</td>
-                </tr>
-                <tr>
-                    <td>Number of links in the articles with the most links</td><td>{statistics.maxOutDegree}</td>
-                </tr>
-                <tr>
-                    <td>Articles which are linked the most</td><td>{statistics.nodesOfMaxInDegree.map(pageName => <a href={pageUrl(statistics.mainPage, pageName).toString()}>{pageName}</a>)}</td>
-                </tr>
-                <tr>
-                    <td>Number of times the most linked article is linked</td><td>{statistics.maxInDegree}</td>
-                </tr>
+                { summary !== undefined && <>
+                    <tr>
+                        <td>Number of articles</td><td>{summary.numberOfNodes}</td>
+                    </tr>
+                    <tr>
+                        <td>Number of links</td><td>{summary.numberOfEdges}</td>
+                    </tr>
+                </> }
+                { degrees !== undefined && <>
+                    <tr>
+                        <td>Articles with the most links</td><td><RankedList mainPage={mainPage} nodes={degrees.topOutDegree}/></td>
+                    </tr>
+                    <tr>
+                        <td>Number of links in the articles with the most links</td><td>{degrees.maxOutDegree}</td>
+                    </tr>
+                    <tr>
+                        <td>Articles which are linked the most</td><td><RankedList mainPage={mainPage} nodes={degrees.topInDegree}/></td>
+                    </tr>
+                    <tr>
+                        <td>Number of times the most linked article is linked</td><td>{degrees.maxInDegree}</td>
+                    </tr>
+                </> }
             </tbody>
         </table>
     </>
-}
\ No newline at end of file
+}